@@ -0,0 +1,46 @@
+//! Generates the module that links every solution of `src/bin` into the library for the `in_process` feature.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let mut bins: Vec<String> = fs::read_dir("src/bin")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter_map(|name| name.strip_suffix(".rs").map(String::from))
+                .filter(|name| is_solution_name(name))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort();
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut modules = String::new();
+
+    for bin in bins {
+        let path = Path::new(&manifest_dir)
+            .join("src")
+            .join("bin")
+            .join(format!("{bin}.rs"));
+
+        modules.push_str(&format!(
+            "#[path = {:?}]\n#[allow(warnings, clippy::all, clippy::pedantic)]\nmod day_{};\n",
+            path.display().to_string(),
+            bin.replace('-', "_")
+        ));
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, modules).unwrap();
+}
+
+/// Solutions are named `YYYY-DD`.
+fn is_solution_name(name: &str) -> bool {
+    name.len() == 7
+        && name
+            .char_indices()
+            .all(|(i, c)| if i == 4 { c == '-' } else { c.is_ascii_digit() })
+}
@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 use regex::Regex;
 use std::str::FromStr;
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 #[derive(Debug)]
 struct Game {
@@ -98,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2286));
     }
 }
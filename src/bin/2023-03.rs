@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use rayon::prelude::*;
 use regex::Regex;
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 fn find_gear_ratio(input: &str, position: usize) -> Option<(u32, u32)> {
     let line_length = input.lines().next().unwrap().len() + 1;
@@ -143,13 +143,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(467835));
     }
 }
@@ -2,7 +2,7 @@ use rayon::prelude::*;
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
 #[derive(Debug)]
 struct Card {
@@ -114,13 +114,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(30));
     }
 }
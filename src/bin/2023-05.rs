@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rayon::prelude::*;
+advent_of_code::solution!(2023, 5, parse = parse_almanac);
+
+#[derive(Debug)]
+pub struct Mapping {
+    src_range: std::ops::Range<usize>,
+    dest_start: usize,
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<usize>,
+    translation_table: HashMap<String, String>,
+    mapping_table: HashMap<String, Vec<Mapping>>,
+}
+
+pub fn parse_almanac(input: &str) -> Almanac {
+    let seeds = {
+        let initial_seeds_regex = regex::Regex::new(r"seeds: (?P<seeds>(?:\d+\s*)+)").unwrap();
+        initial_seeds_regex
+            .captures(input)
+            .unwrap()
+            .name("seeds")
+            .unwrap()
+            .as_str()
+            .split_whitespace()
+            .map(|s| s.parse::<usize>().unwrap())
+            .collect::<Vec<usize>>()
+    };
+    let map_regex =
+        regex::Regex::new(r"(?m)(?P<from>.+)-to-(?P<to>.+) map:\n(?P<values>(?:\d+\s+)+)$")
+            .unwrap();
+    let mut translation_table = HashMap::new();
+    let mut mapping_table: HashMap<String, Vec<Mapping>> = HashMap::new();
+    for capture in map_regex.captures_iter(input) {
+        let from = capture.name("from").unwrap().as_str();
+        let to = capture.name("to").unwrap().as_str();
+        translation_table.insert(from.to_string(), to.to_string());
+
+        let values = capture
+            .name("values")
+            .unwrap()
+            .as_str()
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|s| s.parse::<usize>().unwrap())
+                    .collect_tuple::<(usize, usize, usize)>()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        values.iter().for_each(|(dest, src, len)| {
+            let mapping = mapping_table.entry(to.to_string()).or_default();
+            mapping.push(Mapping {
+                dest_start: *dest,
+                src_range: *src..(*src + len),
+            });
+        });
+    }
+
+    Almanac {
+        seeds,
+        translation_table,
+        mapping_table,
+    }
+}
+
+/// Follows a seed through every map down to its location.
+fn find_location(almanac: &Almanac, seed: usize) -> usize {
+    let mut step = "seed";
+    let mut value = seed;
+    loop {
+        let next_step = almanac.translation_table.get(step);
+        if next_step.is_none() {
+            break;
+        }
+        let next_step = next_step.unwrap();
+
+        let map = almanac.mapping_table.get(next_step).unwrap();
+
+        value = map
+            .iter()
+            .find_map(|xmap| {
+                if xmap.src_range.contains(&value) {
+                    Some(value - xmap.src_range.start + xmap.dest_start)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(value);
+
+        step = next_step;
+    }
+    value
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<usize> {
+    almanac
+        .seeds
+        .par_iter()
+        .map(|seed| find_location(almanac, *seed))
+        .min()
+}
+
+pub fn part_two(almanac: &Almanac) -> Option<usize> {
+    let initial_seeds: Vec<std::ops::Range<usize>> = almanac
+        .seeds
+        .iter()
+        .copied()
+        .tuples::<(usize, usize)>()
+        .map(|(start, end)| start..(start + end))
+        .collect();
+
+    let min_seed = initial_seeds
+        .par_iter()
+        .map(|range| {
+            range
+                .clone()
+                .par_bridge()
+                .map(|seed| (seed, find_location(almanac, seed)))
+                .min_by_key(|&(_, value)| value)
+                .unwrap()
+        })
+        .min_by_key(|&(_, value)| value)
+        .unwrap();
+
+    min_seed.1.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse_almanac(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse_almanac(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
+        assert_eq!(result, Some(46));
+    }
+}
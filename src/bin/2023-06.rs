@@ -1,5 +1,5 @@
 use itertools::Itertools;
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 #[derive(Debug)]
 struct Race {
@@ -82,13 +82,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(71503));
     }
 }
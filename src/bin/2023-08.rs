@@ -3,7 +3,7 @@ use std::{collections::HashMap, str::Chars};
 use num::Integer;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 fn parse_directions(input: &str) -> (Chars, usize) {
     let tmp = input.lines().collect::<Vec<&str>>().first().unwrap().trim();
@@ -28,7 +28,20 @@ fn parse_graph(input: &str) -> HashMap<&str, (&str, &str)> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Follows a direction to the left or right neighbour of a node.
+fn follow<'a>(
+    direction: Option<char>,
+    (left, right): &(&'a str, &'a str),
+) -> Result<&'a str, String> {
+    match direction {
+        Some('L') => Ok(left),
+        Some('R') => Ok(right),
+        Some(direction) => Err(format!("invalid direction '{direction}'")),
+        None => Err("no directions".into()),
+    }
+}
+
+pub fn part_one(input: &str) -> Result<u32, String> {
     const START: &str = "AAA";
     const END: &str = "ZZZ";
 
@@ -39,21 +52,16 @@ pub fn part_one(input: &str) -> Option<u32> {
     let mut current_node = START;
 
     while current_node != END {
-        let (left, right) = nodes.get(current_node).unwrap();
-
-        match directions.clone().nth(required_steps % count_instructions) {
-            Some('L') => current_node = left,
-            Some('R') => current_node = right,
-            _ => panic!("Invalid direction"),
-        }
+        let direction = directions.clone().nth(required_steps % count_instructions);
+        current_node = follow(direction, nodes.get(current_node).unwrap())?;
 
         required_steps += 1;
     }
 
-    (required_steps as u32).into()
+    Ok(required_steps as u32)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Result<usize, String> {
     let (directions, count_instructions) = parse_directions(input);
     let nodes = parse_graph(input);
 
@@ -62,29 +70,21 @@ pub fn part_two(input: &str) -> Option<usize> {
     let depths_to_z: Vec<usize> = current_nodes
         .par_iter()
         .map(|node| {
-            let mut current_node = *node;
+            let mut current_node: &str = node;
             let mut depth = 0;
 
             while !current_node.ends_with('Z') {
-                let (left, right) = nodes.get(current_node).unwrap();
-
-                match directions.clone().nth(depth % count_instructions) {
-                    Some('L') => current_node = left,
-                    Some('R') => current_node = right,
-                    _ => panic!("Invalid direction"),
-                }
+                let direction = directions.clone().nth(depth % count_instructions);
+                current_node = follow(direction, nodes.get(current_node).unwrap())?;
 
                 depth += 1;
             }
 
-            depth
+            Ok(depth)
         })
-        .collect();
+        .collect::<Result<_, String>>()?;
 
-    depths_to_z
-        .iter()
-        .fold(1, |acc, depth| acc.lcm(depth))
-        .into()
+    Ok(depths_to_z.iter().fold(1, |acc, depth| acc.lcm(depth)))
 }
 
 #[cfg(test)]
@@ -93,13 +93,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        let result = part_one(&advent_of_code::template::read_example(YEAR, DAY, 1, 0));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        let result = part_two(&advent_of_code::template::read_example(YEAR, DAY, 2, 0));
+        assert_eq!(result, Ok(6));
     }
 }
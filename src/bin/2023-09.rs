@@ -1,5 +1,5 @@
 use rayon::prelude::*;
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 fn predict_next_diff(readings: &[isize]) -> isize {
     if readings.iter().all(|&n| n == 0) {
@@ -59,13 +59,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2));
     }
 }
@@ -1,11 +1,11 @@
 use colored::Colorize;
 use rayon::prelude::*;
 use std::{collections::HashSet, fmt::Display, str::FromStr};
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10, parse = parse_matrix);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(u8)]
-enum Tile {
+pub enum Tile {
     VerticalPipe = b'|',
     HorizontalPipe = b'-',
     BendNorthEast = b'L',
@@ -295,8 +295,10 @@ fn print_path(matrix: &[Vec<Tile>], path: &[(usize, usize)]) {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let matrix = input
+type Matrix = Vec<Vec<Tile>>;
+
+pub fn parse_matrix(input: &str) -> Matrix {
+    input
         .par_lines()
         .map(|line| {
             line.par_chars()
@@ -307,16 +309,18 @@ pub fn part_one(input: &str) -> Option<usize> {
                 })
                 .collect::<Vec<Tile>>()
         })
-        .collect::<Vec<_>>();
+        .collect::<Vec<_>>()
+}
 
-    let initial_position = find_s(&matrix);
+pub fn part_one(matrix: &Matrix) -> Option<usize> {
+    let initial_position = find_s(matrix);
     let mut current_position = initial_position;
     let mut visited = vec![(0, 0), initial_position];
     let mut steps = 0;
 
     loop {
         let new_position = find_first_connection(
-            &matrix,
+            matrix,
             current_position,
             if visited.len() > 1 {
                 Some(visited[visited.len() - 2])
@@ -334,33 +338,20 @@ pub fn part_one(input: &str) -> Option<usize> {
     }
 
     if false {
-        print_path(&matrix, &visited);
+        print_path(matrix, &visited);
     }
 
     Some(steps / 2)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let matrix = input
-        .par_lines()
-        .map(|line| {
-            line.par_chars()
-                .map(|char| {
-                    char.to_string()
-                        .parse::<Tile>()
-                        .unwrap_or_else(|_| panic!("Invalid char: {}", char))
-                })
-                .collect::<Vec<Tile>>()
-        })
-        .collect::<Vec<_>>();
-
-    let initial_position = find_s(&matrix);
+pub fn part_two(matrix: &Matrix) -> Option<usize> {
+    let initial_position = find_s(matrix);
     let mut current_position = initial_position;
     let mut visited: Vec<(usize, usize)> = vec![initial_position];
 
     loop {
         let new_position = find_first_connection(
-            &matrix,
+            matrix,
             current_position,
             if visited.len() > 1 {
                 Some(visited[visited.len() - 2])
@@ -442,7 +433,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     if true {
         print_path_2(
-            &matrix,
+            matrix,
             &visited_set,
             &inside_set,
             &contained_set,
@@ -484,13 +475,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_matrix(&advent_of_code::template::read_example(
+            YEAR, DAY, 1, 0,
+        )));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_matrix(&advent_of_code::template::read_example(
+            YEAR, DAY, 2, 0,
+        )));
         assert_eq!(result, Some(4));
     }
 }
@@ -1,6 +1,6 @@
 use rayon::prelude::*;
 use std::collections::HashMap;
-advent_of_code::solution!(11);
+advent_of_code::solution!(2023, 11);
 
 fn expand_empty_columns(grid: &mut Vec<Vec<char>>, replacement: char) {
     let mut empty_columns = Vec::new();
@@ -171,13 +171,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
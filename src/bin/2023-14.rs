@@ -2,7 +2,7 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use std::str::FromStr;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 const ROTATION: [Direction; 4] = [
     Direction::North,
@@ -193,13 +193,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(64));
     }
 }
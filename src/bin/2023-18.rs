@@ -1,10 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
 use fnv::FnvHashSet;
+use lazy_static::lazy_static;
 use rayon::{join, prelude::*};
 use regex::Regex;
 use std::str::FromStr;
-advent_of_code::solution!(18);
+advent_of_code::solution!(2023, 18);
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Direction {
@@ -66,7 +65,7 @@ impl FromStr for Task {
             .ok_or("Couldn't parse distance")?
             .as_str()
             .parse::<usize>()
-            .unwrap();
+            .map_err(|_| "Couldn't parse distance")?;
 
         let captures_part2 = PART2_LINE_REGEX.captures(s).ok_or("Couldn't parse line")?;
         let p2_direction_no = captures_part2
@@ -87,7 +86,7 @@ impl FromStr for Task {
                 .as_str(),
             16,
         )
-        .unwrap();
+        .map_err(|_| "Couldn't parse distance")?;
 
         Ok(Task {
             direction,
@@ -98,12 +97,11 @@ impl FromStr for Task {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> Result<usize, &'static str> {
     let tasks: Vec<Task> = input
         .par_lines()
         .map(Task::from_str)
-        .map(Result::unwrap)
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let mut current_width: isize = 0;
     let mut min_width: isize = 0;
@@ -241,15 +239,18 @@ pub fn part_one(input: &str) -> Option<usize> {
         }
     }
 
-    (inside_set.len() + count).into()
+    Ok(inside_set.len() + count)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Result<usize, &'static str> {
     let tasks: Vec<Task> = input
         .par_lines()
         .map(Task::from_str)
-        .map(Result::unwrap)
-        .collect();
+        .collect::<Result<_, _>>()?;
+
+    if tasks.is_empty() {
+        return Err("No dig plan");
+    }
 
     let (perimeter_distance, corners) = join(
         || tasks.par_iter().map(|task| task.p2_distance).sum::<usize>(),
@@ -279,7 +280,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     sum -= corners[0].0 * corners[corners.len() - 1].1;
 
     let area = (sum.abs() / 2) as usize;
-    Some(area + (perimeter_distance / 2 + 1))
+    Ok(area + (perimeter_distance / 2 + 1))
 }
 
 #[cfg(test)]
@@ -288,13 +289,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(62));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(62));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(952408144115));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        assert_eq!(result, Ok(952408144115));
     }
 }
@@ -1,4 +1,15 @@
+// lets `solution!` refer to the library by name when solutions are linked into it.
+extern crate self as advent_of_code;
+
 mod day;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use year::*;
+
+/// Every solution of `src/bin`, linked into the library so they can be run in-process.
+#[cfg(feature = "in_process")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
@@ -1,27 +1,59 @@
-use advent_of_code::template::commands::{all, download, scaffold, solve};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, status, watch};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::aoc_client::get_year;
+    use advent_of_code::template::{
+        benchmark_export::parse_export,
+        benchmark_history::Baseline,
+        commands::{
+            all::AllOptions,
+            solve::SolveOptions,
+            status::{self, StatusOptions},
+        },
+        parse_duration, parse_part, parse_threads,
+        runner::BenchConfig,
+    };
+    use advent_of_code::{Day, Year};
 
     pub enum AppArguments {
         Download {
+            year: Year,
+            day: Day,
+            example: bool,
+            html: Option<String>,
+        },
+        Read {
+            year: Year,
             day: Day,
+            html: Option<String>,
         },
         Scaffold {
+            year: Year,
             day: Day,
+            example: bool,
+            html: Option<String>,
+            template: Option<String>,
         },
         Solve {
+            year: Year,
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: SolveOptions,
         },
         All {
+            year: Year,
+            options: AllOptions,
+        },
+        Watch {
+            year: Year,
+            day: Day,
             release: bool,
-            time: bool,
+        },
+        Status {
+            year: Year,
+            options: StatusOptions,
         },
     }
 
@@ -30,20 +62,69 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                year: parse_year(&mut args)?,
+                options: AllOptions {
+                    is_release: args.contains("--release"),
+                    is_timed: args.contains("--time"),
+                    bench_config: parse_bench_config(&mut args)?,
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    part: args.opt_value_from_fn("--part", parse_part)?,
+                    in_process: args.contains("--in-process"),
+                    threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+                    export: args.opt_value_from_fn("--export", parse_export)?,
+                    threads: args.opt_value_from_fn("--threads", parse_threads)?,
+                    // parsed last, so that a bare `--compare` is not followed by another option.
+                    compare: parse_compare(&mut args)?,
+                },
             },
             Some("download") => AppArguments::Download {
+                year: parse_year(&mut args)?,
                 day: args.free_from_str()?,
+                example: args.contains("--example"),
+                html: args.opt_value_from_str("--html")?,
+            },
+            Some("read") => AppArguments::Read {
+                year: parse_year(&mut args)?,
+                day: args.free_from_str()?,
+                html: args.opt_value_from_str("--html")?,
             },
             Some("scaffold") => AppArguments::Scaffold {
+                year: parse_year(&mut args)?,
                 day: args.free_from_str()?,
+                example: args.contains("--example"),
+                html: args.opt_value_from_str("--html")?,
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
+                year: parse_year(&mut args)?,
+                day: args.free_from_str()?,
+                options: SolveOptions {
+                    release: args.contains("--release"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    part: args.opt_value_from_fn("--part", parse_part)?,
+                    input: args.opt_value_from_str("--input")?,
+                    time: args.contains("--time"),
+                    bench_config: parse_bench_config(&mut args)?,
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    threads: args.opt_value_from_fn("--threads", parse_threads)?,
+                    scaling: args.contains("--scaling"),
+                },
+            },
+            Some("status") => AppArguments::Status {
+                year: parse_year(&mut args)?,
+                options: StatusOptions {
+                    is_release: !args.contains("--debug"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    timeout: args
+                        .opt_value_from_fn("--timeout", parse_duration)?
+                        .unwrap_or(status::DEFAULT_TIMEOUT),
+                },
+            },
+            Some("watch") => AppArguments::Watch {
+                year: parse_year(&mut args)?,
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -62,6 +143,36 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// The year defaults to `AOC_YEAR` if `--year` is not passed.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => get_year().ok_or_else(|| "no year given. Pass --year or set AOC_YEAR.".into()),
+        }
+    }
+
+    /// `--compare` takes an optional commit to compare with, defaulting to the latest run.
+    fn parse_compare(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Baseline>, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str::<_, String>("--compare") {
+            Ok(commit) => Ok(commit.map(Baseline::Commit)),
+            Err(pico_args::Error::OptionWithoutAValue(_)) => {
+                Ok(args.contains("--compare").then_some(Baseline::Latest))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        Ok(BenchConfig {
+            samples: args.opt_value_from_str("--samples")?,
+            budget: args.opt_value_from_fn("--budget", parse_duration)?,
+        })
+    }
 }
 
 fn main() {
@@ -71,15 +182,24 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All { year, options } => all::handle(year, options),
+            AppArguments::Download {
+                year,
+                day,
+                example,
+                html,
+            } => download::handle(year, day, example, html.as_deref()),
+            AppArguments::Read { year, day, html } => read::handle(year, day, html.as_deref()),
+            AppArguments::Scaffold {
+                year,
                 day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+                example,
+                html,
+                template,
+            } => scaffold::handle(year, day, example, html.as_deref(), template.as_deref()),
+            AppArguments::Solve { year, day, options } => solve::handle(year, day, options),
+            AppArguments::Status { year, options } => status::handle(year, options),
+            AppArguments::Watch { year, day, release } => watch::handle(year, day, release),
         },
     };
 }
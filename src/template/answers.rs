@@ -0,0 +1,240 @@
+/// Module that stores known answers and verifies solution output against them.
+/// Answers live in `data/YYYY/answers/NN.toml`, e.g.:
+///
+/// ```toml
+/// part_one = "142"
+/// part_two = 281
+/// ```
+use std::{env, fs, io};
+
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The recorded answers of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Returns the recorded answer for `part`, if there is one.
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compares a computed result with the recorded answer for `part`.
+    #[must_use]
+    pub fn verify(&self, part: u8, result: &str) -> Verification {
+        match self.get(part) {
+            None => Verification::Unknown,
+            Some(expected) if expected == result => Verification::Verified,
+            Some(expected) => Verification::Mismatch(expected.to_string()),
+        }
+    }
+}
+
+/// The outcome of comparing a result with the answer store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Verified,
+    Mismatch(String),
+    Unknown,
+}
+
+#[must_use]
+pub fn get_path_for_answers(year: Year, day: Day) -> String {
+    format!("data/{year}/answers/{day}.toml")
+}
+
+/// Reads the recorded answers for a day. A missing file means no answers are known yet.
+pub fn read(year: Year, day: Day) -> Result<Answers, Error> {
+    let filepath = env::current_dir()?.join(get_path_for_answers(year, day));
+
+    match fs::read_to_string(filepath) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Records the answer of a part, keeping the answer of the other part.
+pub fn record(year: Year, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read(year, day)?;
+
+    match part {
+        1 => answers.part_one = Some(answer.to_string()),
+        2 => answers.part_two = Some(answer.to_string()),
+        _ => return Err(Error::Parser(format!("invalid part {part}."))),
+    }
+
+    let path = env::current_dir()?.join(get_path_for_answers(year, day));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, to_toml(&answers))?;
+    Ok(())
+}
+
+fn to_toml(answers: &Answers) -> String {
+    [
+        ("part_one", &answers.part_one),
+        ("part_two", &answers.part_two),
+    ]
+    .iter()
+    .filter_map(|(key, value)| {
+        let value = value.as_ref()?;
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        Some(format!("{key} = \"{escaped}\"\n"))
+    })
+    .collect()
+}
+
+/// Parses the small subset of TOML used by answer files: `key = "string"` or `key = 123` pairs.
+fn parse(s: &str) -> Result<Answers, Error> {
+    let mut answers = Answers::default();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::Parser(format!("line {}: expected `key = value`.", i + 1)))?;
+
+        let value = parse_value(value.trim())
+            .ok_or_else(|| Error::Parser(format!("line {}: invalid value.", i + 1)))?;
+
+        match key.trim() {
+            "part_one" => answers.part_one = Some(value),
+            "part_two" => answers.part_two = Some(value),
+            key => {
+                return Err(Error::Parser(format!(
+                    "line {}: unknown key `{key}`.",
+                    i + 1
+                )))
+            }
+        }
+    }
+
+    Ok(answers)
+}
+
+fn parse_value(s: &str) -> Option<String> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    let rest = chars.as_str().trim();
+                    return (rest.is_empty() || rest.starts_with('#')).then_some(value);
+                }
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    c @ ('"' | '\\') => value.push(c),
+                    _ => return None,
+                },
+                c => value.push(c),
+            }
+        }
+
+        None
+    } else {
+        // answers can exceed `i64`, and are compared as they are displayed, e.g. without a `+`.
+        let value = s.split('#').next()?.trim();
+        value
+            .parse::<u128>()
+            .map(|value| value.to_string())
+            .or_else(|_| value.parse::<i128>().map(|value| value.to_string()))
+            .ok()
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{parse, to_toml, Answers, Verification};
+
+    #[test]
+    fn parses_strings_and_integers() {
+        let answers = parse("# day 1\npart_one = \"abc\"\npart_two = 281 # comment\n").unwrap();
+        assert_eq!(answers.part_one.as_deref(), Some("abc"));
+        assert_eq!(answers.part_two.as_deref(), Some("281"));
+    }
+
+    #[test]
+    fn parses_large_integers() {
+        let answers = parse(
+            "part_one = 18446744073709551616
+part_two = -42",
+        )
+        .unwrap();
+        assert_eq!(answers.part_one.as_deref(), Some("18446744073709551616"));
+        assert_eq!(answers.part_two.as_deref(), Some("-42"));
+        assert_eq!(
+            parse("part_one = +7").unwrap().part_one.as_deref(),
+            Some("7")
+        );
+        assert!(parse("part_one = 1.5").is_err());
+    }
+
+    #[test]
+    fn parses_escaped_multiline_answers() {
+        let answers = parse(r##"part_one = "#.\n.#""##).unwrap();
+        assert_eq!(answers.part_one.as_deref(), Some("#.\n.#"));
+        assert_eq!(answers.part_two, None);
+    }
+
+    #[test]
+    fn answers_survive_round_trip() {
+        let answers = Answers {
+            part_one: Some("a \"b\" \\ c\n\td".into()),
+            part_two: None,
+        };
+        assert_eq!(parse(&to_toml(&answers)).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(parse("part_three = 1").is_err());
+        assert!(parse("part_one = \"unterminated").is_err());
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = Answers {
+            part_one: Some("42".into()),
+            part_two: None,
+        };
+        assert_eq!(answers.verify(1, "42"), Verification::Verified);
+        assert_eq!(answers.verify(1, "41"), Verification::Mismatch("42".into()));
+        assert_eq!(answers.verify(2, "41"), Verification::Unknown);
+    }
+}
@@ -0,0 +1,306 @@
+/// A small HTTP client for adventofcode.com that downloads inputs and submits answers.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or, if unset,
+/// from `~/.adventofcode.session` (the same file aoc-cli uses).
+/// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point the client to a local mock server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::puzzle::{decode_entities, strip_tags};
+use crate::{Day, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or config file.
+    MissingSession,
+    /// The server rejected the session cookie.
+    Unauthorized,
+    /// The server asked us to slow down.
+    RateLimited,
+    /// The puzzle has not been released yet.
+    NotUnlocked,
+    /// The server responded with an unexpected status code.
+    BadStatus(u16),
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to ~/{SESSION_FILE_NAME}."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::RateLimited => {
+                write!(f, "too many requests, wait a moment before trying again.")
+            }
+            AocClientError::NotUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with unexpected status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IoError(e) => write!(f, "could not read or write file: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            // AoC answers requests with an invalid or missing session with 400 or 500.
+            ureq::Error::Status(400 | 401 | 403 | 500, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(404, _) => AocClientError::NotUnlocked,
+            ureq::Error::Status(429, _) => AocClientError::RateLimited,
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&format!(
+                "{}/{} ({})",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_AUTHORS")
+            ))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client for the given year from `AOC_BASE_URL` and the configured session cookie.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    #[must_use]
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    fn get_day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Downloads the puzzle input of a day.
+    pub fn download_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.get_day_url(day)))
+    }
+
+    /// Downloads the HTML of a day's puzzle page.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&self.get_day_url(day))
+    }
+
+    /// Submits an answer and returns the text of the server's response message.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.get_day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let body = response.into_string()?;
+
+        // the server answers with a login page rather than an error status if the session is invalid.
+        if body.contains("To play, please identify yourself") {
+            return Err(AocClientError::Unauthorized);
+        }
+
+        Ok(extract_article_text(&body))
+    }
+}
+
+/// Reads the default year of the event from `AOC_YEAR`.
+#[must_use]
+pub fn get_year() -> Option<Year> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok().or(None),
+        Err(_) => None,
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let path = PathBuf::from(home).join(SESSION_FILE_NAME);
+    fs::read_to_string(path)
+        .ok()
+        .filter(|session| !session.trim().is_empty())
+}
+
+/// Returns the text content of the first `<article>` of a page, or of the whole page if it has none.
+#[must_use]
+pub fn extract_article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    decode_entities(&strip_tags(article)).trim().to_string()
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{extract_article_text, AocClient, AocClientError};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serves a single request with the given status and body and returns the base URL and the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (url, server) = mock_server(200, "1abc2\n");
+        let client = AocClient::new(&url, "secret\n", year!(2023));
+
+        assert_eq!(client.download_input(day!(1)).unwrap(), "1abc2\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn maps_error_statuses() {
+        let (url, _) = mock_server(400, "Puzzle inputs differ by user.");
+        let client = AocClient::new(&url, "secret", year!(2023));
+        assert!(matches!(
+            client.download_input(day!(1)),
+            Err(AocClientError::Unauthorized)
+        ));
+
+        let (url, _) = mock_server(404, "Please don't repeatedly request this endpoint.");
+        let client = AocClient::new(&url, "secret", year!(2023));
+        assert!(matches!(
+            client.download_input(day!(25)),
+            Err(AocClientError::NotUnlocked)
+        ));
+
+        let (url, _) = mock_server(429, "");
+        let client = AocClient::new(&url, "secret", year!(2023));
+        assert!(matches!(
+            client.download_input(day!(1)),
+            Err(AocClientError::RateLimited)
+        ));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(
+            200,
+            "<main><article><p>That's the <em>right</em> answer!</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret", year!(2023));
+
+        assert_eq!(
+            client.submit(day!(3), 2, "4361").unwrap(),
+            "That's the right answer!"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=4361"));
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        assert_eq!(
+            extract_article_text("<article class=\"day-desc\"><p>a &lt; b &amp; c</p></article>"),
+            "a < b & c"
+        );
+        assert_eq!(extract_article_text("plain"), "plain");
+    }
+}
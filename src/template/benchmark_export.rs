@@ -0,0 +1,400 @@
+/// Module that exports the timings of an `all` run for use outside of the README.
+/// The format is picked by the extension of the file: `.csv`, `.json` or `.svg`.
+/// CSV and JSON contain one row per part and one for the parse step, if the solution has one.
+/// The SVG is a self-contained bar chart that stacks the parse step and the parts of each day.
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::template::json::ObjectWriter;
+use crate::template::readme_benchmarks::Timings;
+use crate::template::runner::{BenchStats, PARSE_STEP};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+const CSV_HEADER: &str = "year,day,part,median_ns,mean_ns,min_ns,max_ns,stddev_ns,p95_ns,samples,allocations,allocated_bytes,peak_bytes";
+
+const CHART_HEIGHT: f64 = 240.0;
+const BAR_WIDTH: f64 = 20.0;
+/// Leaves room for the title if only a few days ran.
+const MIN_WIDTH: f64 = 320.0;
+const BAR_GAP: f64 = 8.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_TOP: f64 = 48.0;
+const MARGIN_BOTTOM: f64 = 48.0;
+/// The stacked segments of a bar, from the bottom up: step, title and color.
+const SEGMENTS: [(u8, &str, &str); 3] = [
+    (PARSE_STEP, "Parse", "#bab0ac"),
+    (1, "Part 1", "#4e79a7"),
+    (2, "Part 2", "#f28e2b"),
+];
+
+/// The parse step and every part that ran, in order of day and part.
+fn steps(timings: &[Timings]) -> impl Iterator<Item = (Day, u8, &BenchStats)> {
+    timings.iter().flat_map(|timing| {
+        [PARSE_STEP, 1, 2]
+            .into_iter()
+            .filter_map(move |step| Some((timing.day, step, timing.get(step).0?)))
+    })
+}
+
+/// Name of a step in the `part` column, `parse` for the parse step.
+fn get_step_name(step: u8) -> String {
+    match step {
+        PARSE_STEP => "parse".into(),
+        part => part.to_string(),
+    }
+}
+
+#[must_use]
+pub fn to_csv(year: Year, timings: &[Timings]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+
+    for (day, step, stats) in steps(timings) {
+        let alloc = stats.alloc.map_or_else(
+            || ",,".into(),
+            |alloc| format!("{},{},{}", alloc.allocations, alloc.bytes, alloc.peak_bytes),
+        );
+
+        let _ = write!(
+            csv,
+            "\n{year},{day},{},{},{},{},{},{},{},{},{alloc}",
+            get_step_name(step),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.min.as_nanos(),
+            stats.max.as_nanos(),
+            stats.stddev.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.samples,
+        );
+    }
+
+    csv.push('\n');
+    csv
+}
+
+#[must_use]
+pub fn to_json(year: Year, timings: &[Timings]) -> String {
+    let rows: Vec<String> = steps(timings)
+        .map(|(day, step, stats)| {
+            let writer = ObjectWriter::new()
+                .number("year", year.into_inner())
+                .number("day", day.into_inner())
+                .string("part", &get_step_name(step))
+                .number("median_ns", stats.median.as_nanos())
+                .number("mean_ns", stats.mean.as_nanos())
+                .number("min_ns", stats.min.as_nanos())
+                .number("max_ns", stats.max.as_nanos())
+                .number("stddev_ns", stats.stddev.as_nanos())
+                .number("p95_ns", stats.p95.as_nanos())
+                .number("samples", stats.samples);
+
+            match stats.alloc {
+                Some(alloc) => writer
+                    .number("allocations", alloc.allocations)
+                    .number("allocated_bytes", alloc.bytes)
+                    .number("peak_bytes", alloc.peak_bytes),
+                None => writer,
+            }
+            .finish()
+        })
+        .collect();
+
+    if rows.is_empty() {
+        "[]\n".into()
+    } else {
+        format!("[\n  {}\n]\n", rows.join(",\n  "))
+    }
+}
+
+/// Renders a bar per day. Bars are on a log scale, split between the parse step and the parts
+/// by their share of the day's time.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn to_svg(year: Year, timings: &[Timings]) -> String {
+    let median = |stats: Option<&BenchStats>| stats.map_or(0.0, |s| s.median.as_nanos() as f64);
+    let days: Vec<(Day, Vec<f64>)> = timings
+        .iter()
+        .map(|timing| {
+            let segments: Vec<f64> = SEGMENTS
+                .iter()
+                .map(|(step, _, _)| median(timing.get(*step).0))
+                .collect();
+            (timing.day, segments)
+        })
+        .filter(|(_, segments)| segments.iter().sum::<f64>() > 0.0)
+        .collect();
+
+    // the axis spans whole decades of nanoseconds around the fastest and slowest day.
+    let totals = days
+        .iter()
+        .map(|(_, segments)| segments.iter().sum::<f64>().log10());
+    let min_decade = match totals.clone().fold(f64::INFINITY, f64::min).floor() {
+        decade if decade.is_finite() => decade,
+        _ => 0.0,
+    };
+    let max_decade = totals.fold(0.0, f64::max).ceil().max(min_decade + 1.0);
+
+    let scale = |nanos: f64| {
+        let value = (nanos.log10() - min_decade) / (max_decade - min_decade);
+        value.clamp(0.0, 1.0) * CHART_HEIGHT
+    };
+
+    let chart_width = days.len().max(1) as f64 * (BAR_WIDTH + BAR_GAP) + BAR_GAP;
+    let width = (MARGIN_LEFT + chart_width + 16.0).max(MIN_WIDTH);
+    let height = MARGIN_TOP + CHART_HEIGHT + MARGIN_BOTTOM;
+    let baseline = MARGIN_TOP + CHART_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="white"/>"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="20" font-size="14" font-weight="bold">Advent of Code {year}: median per day</text>"#
+    );
+
+    // the legend leaves out the parse step if no day has one.
+    let legend = SEGMENTS
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| days.iter().any(|(_, segments)| segments[*i] > 0.0));
+
+    for (i, (_, (_, title, color))) in legend.enumerate() {
+        let x = MARGIN_LEFT + i as f64 * 64.0;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="28" width="10" height="10" fill="{color}"/><text x="{}" y="37">{title}</text>"#,
+            x + 14.0
+        );
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    for decade in min_decade as u32..=max_decade as u32 {
+        let nanos = 10_f64.powi(decade as i32);
+        let y = baseline - scale(nanos);
+        let label = format!("{:?}", std::time::Duration::from_nanos(10_u64.pow(decade)));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{y}" x2="{}" y2="{y}" stroke="#ddd"/><text x="{}" y="{}" text-anchor="end">{label}</text>"##,
+            MARGIN_LEFT + chart_width,
+            MARGIN_LEFT - 6.0,
+            y + 4.0
+        );
+    }
+
+    for (i, (day, segments)) in days.iter().enumerate() {
+        let x = MARGIN_LEFT + BAR_GAP + i as f64 * (BAR_WIDTH + BAR_GAP);
+        let total: f64 = segments.iter().sum();
+        let bar_height = scale(total);
+        let mut y = baseline;
+
+        for (nanos, (_, _, color)) in segments.iter().zip(SEGMENTS) {
+            if *nanos == 0.0 {
+                continue;
+            }
+
+            let height = bar_height * nanos / total;
+            y -= height;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{BAR_WIDTH}" height="{height}" fill="{color}"/>"#
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            x + BAR_WIDTH / 2.0,
+            baseline + 16.0,
+            day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{baseline}" x2="{}" y2="{baseline}" stroke="#333"/>"##,
+        MARGIN_LEFT + chart_width
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">Day</text>"#,
+        MARGIN_LEFT + chart_width / 2.0,
+        baseline + 36.0
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Format of an export, picked by the extension of the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Svg,
+}
+
+/// File to export the timings of a run to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    pub path: String,
+    pub format: ExportFormat,
+}
+
+/// Parses the path of an export, which must end in `.csv`, `.json` or `.svg`.
+pub fn parse_export(s: &str) -> Result<Export, &'static str> {
+    let extension = Path::new(s)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    let format = match extension.as_deref() {
+        Some("csv") => ExportFormat::Csv,
+        Some("json") => ExportFormat::Json,
+        Some("svg") => ExportFormat::Svg,
+        _ => return Err("unknown export format, expecting a .csv, .json or .svg file"),
+    };
+
+    Ok(Export {
+        path: s.to_string(),
+        format,
+    })
+}
+
+/// Writes the timings to the file of an export.
+pub fn export(export: &Export, year: Year, timings: &[Timings]) -> Result<(), Error> {
+    let content = match export.format {
+        ExportFormat::Csv => to_csv(year, timings),
+        ExportFormat::Json => to_json(year, timings),
+        ExportFormat::Svg => to_svg(year, timings),
+    };
+
+    if let Some(parent) = Path::new(&export.path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&export.path, content)?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{parse_export, to_csv, to_json, to_svg, ExportFormat};
+    use crate::template::allocator::AllocStats;
+    use crate::template::json;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::BenchStats;
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn get_mock_timings() -> Vec<Timings> {
+        let stats = |micros: u64| {
+            Some(BenchStats {
+                samples: 10,
+                ..BenchStats::single(Duration::from_micros(micros))
+            })
+        };
+
+        vec![
+            Timings {
+                parse: None,
+                day: day!(1),
+                part_1: stats(10),
+                part_2: stats(2000).map(|stats| BenchStats {
+                    alloc: Some(AllocStats {
+                        allocations: 3,
+                        bytes: 1024,
+                        peak_bytes: 512,
+                    }),
+                    ..stats
+                }),
+                status_1: None,
+                status_2: None,
+                total_nanos: 2_010_000.0,
+            },
+            Timings {
+                parse: None,
+                day: day!(2),
+                part_1: stats(300),
+                part_2: None,
+                status_1: None,
+                status_2: None,
+                total_nanos: 300_000.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = to_csv(year!(2023), &get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("year,day,part,median_ns"));
+        assert_eq!(lines[1], "2023,01,1,10000,10000,10000,10000,0,10000,10,,,");
+        assert!(lines[2].ends_with(",10,3,1024,512"));
+        assert!(lines[3].starts_with("2023,02,1,300000,"));
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = to_json(year!(2023), &get_mock_timings());
+        let rows: Vec<_> = json
+            .lines()
+            .filter(|line| line.trim_start().starts_with('{'))
+            .map(|line| json::parse_object(line.trim().trim_end_matches(',')).unwrap())
+            .collect();
+
+        assert!(json.starts_with('[') && json.trim_end().ends_with(']'));
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1]["part"].as_str(), Some("2"));
+        assert_eq!(rows[1]["peak_bytes"].as_u128(), Some(512));
+        assert!(!rows[0].contains_key("allocations"));
+        assert_eq!(to_json(year!(2023), &[]), "[]\n");
+    }
+
+    #[test]
+    fn exports_svg() {
+        let svg = to_svg(year!(2023), &get_mock_timings());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("Advent of Code 2023"));
+        // two days with two and one parts.
+        assert_eq!(svg.matches(r#"width="20""#).count(), 3);
+        assert!(!svg.contains("Parse"));
+    }
+
+    #[test]
+    fn parses_export_formats() {
+        let format = |path: &str| parse_export(path).map(|export| export.format);
+        assert_eq!(format("out/benchmarks.csv"), Ok(ExportFormat::Csv));
+        assert_eq!(format("benchmarks.JSON"), Ok(ExportFormat::Json));
+        assert_eq!(format("benchmarks.svg"), Ok(ExportFormat::Svg));
+        assert!(format("benchmarks.txt").is_err());
+        assert!(format("benchmarks").is_err());
+    }
+
+    #[test]
+    fn exports_parse_step() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some(BenchStats::single(Duration::from_micros(50)));
+
+        let csv = to_csv(year!(2023), &timings);
+        assert!(csv.contains("\n2023,02,parse,50000,"));
+        assert!(to_json(year!(2023), &timings).contains(r#""part":"parse""#));
+        assert!(to_svg(year!(2023), &timings).contains(">Parse</text>"));
+    }
+}
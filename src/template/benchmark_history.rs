@@ -0,0 +1,303 @@
+/// Module that keeps the timings of every timed `all` run, so that runs can be compared with each other.
+/// Every run is appended to `data/YYYY/benchmarks.jsonl`, one line per run, e.g.:
+///
+/// ```json
+/// {"timestamp":1700000000,"commit":"1a2b3c4","release":true,"01-1":74210,"01-2":130002}
+/// ```
+///
+/// Keys of the form `NN-P` hold the median of a part in nanoseconds, `NN-0` the one of the parse step.
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::Duration,
+};
+
+use crate::template::json::{self, ObjectWriter};
+use crate::template::readme_benchmarks::Timings;
+use crate::template::runner::PARSE_STEP;
+use crate::template::submissions::now;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The timings of a single timed run of `all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out commit, if the repository is a git repository.
+    pub commit: Option<String>,
+    pub release: bool,
+    /// Median of every part that ran and of the parse step, in nanoseconds.
+    pub timings: BTreeMap<(Day, u8), u64>,
+}
+
+impl Run {
+    /// Creates a run from the timings collected by `all`, stamped with the current time and commit.
+    #[must_use]
+    pub fn new(timings: &[Timings], release: bool) -> Self {
+        let timings = timings
+            .iter()
+            .flat_map(|t| {
+                [
+                    (t.day, PARSE_STEP, t.parse),
+                    (t.day, 1, t.part_1),
+                    (t.day, 2, t.part_2),
+                ]
+            })
+            .filter_map(|(day, part, stats)| {
+                let nanos = u64::try_from(stats?.median.as_nanos()).ok()?;
+                Some(((day, part), nanos))
+            })
+            .collect();
+
+        Self {
+            timestamp: now(),
+            commit: get_commit(),
+            release,
+            timings,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let writer = ObjectWriter::new()
+            .number("timestamp", self.timestamp)
+            .opt_string("commit", self.commit.as_deref())
+            .bool("release", self.release);
+
+        self.timings
+            .iter()
+            .fold(writer, |writer, ((day, part), nanos)| {
+                writer.number(&format!("{day}-{part}"), *nanos)
+            })
+            .finish()
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+
+        let mut timings = BTreeMap::new();
+
+        for (key, value) in &object {
+            let Some((day, part)) = key.split_once('-') else {
+                continue;
+            };
+            let key = (day.parse().ok()?, part.parse().ok()?);
+            timings.insert(key, u64::try_from(value.as_u128()?).ok()?);
+        }
+
+        Some(Self {
+            timestamp: u64::try_from(object.get("timestamp")?.as_u128()?).ok()?,
+            commit: object
+                .get("commit")
+                .and_then(|commit| commit.as_str())
+                .map(String::from),
+            release: object.get("release")?.as_bool()?,
+            timings,
+        })
+    }
+}
+
+/// The run to compare a new run with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Baseline {
+    /// The most recent run with the same profile.
+    Latest,
+    /// The most recent run of a commit, given by a prefix of its hash.
+    Commit(String),
+}
+
+/// Finds the baseline among the recorded runs. Only runs with the same profile are considered,
+/// as debug and release timings are not comparable.
+#[must_use]
+pub fn find_baseline<'a>(runs: &'a [Run], baseline: &Baseline, release: bool) -> Option<&'a Run> {
+    runs.iter()
+        .rev()
+        .filter(|run| run.release == release)
+        .find(|run| match baseline {
+            Baseline::Latest => true,
+            Baseline::Commit(prefix) => run
+                .commit
+                .as_deref()
+                .is_some_and(|commit| commit.starts_with(prefix.as_str())),
+        })
+}
+
+/// The change of a part's timing relative to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+    /// Relative change in percent, positive if the part got slower.
+    pub percent: f64,
+}
+
+impl Delta {
+    /// Whether the part got slower by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent > threshold
+    }
+}
+
+/// Compares every part that ran in both runs.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compare(baseline: &Run, run: &Run) -> Vec<Delta> {
+    run.timings
+        .iter()
+        .filter_map(|(&(day, part), &after)| {
+            let before = *baseline.timings.get(&(day, part))?;
+            let percent = if before == 0 {
+                0.0
+            } else {
+                (after as f64 - before as f64) / before as f64 * 100.0
+            };
+
+            Some(Delta {
+                day,
+                part,
+                before: Duration::from_nanos(before),
+                after: Duration::from_nanos(after),
+                percent,
+            })
+        })
+        .collect()
+}
+
+/// Short hash of `HEAD`, or [`None`] if git is not available.
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !commit.trim().is_empty()).then(|| commit.trim().to_string())
+}
+
+#[must_use]
+pub fn get_path_for_history(year: Year) -> String {
+    format!("data/{year}/benchmarks.jsonl")
+}
+
+/// Reads all recorded runs of a year, oldest first. A missing file means no run was recorded yet.
+pub fn read(year: Year) -> Result<Vec<Run>, Error> {
+    let content = match fs::read_to_string(get_path_for_history(year)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            Run::from_json(line)
+                .ok_or_else(|| Error::Parser(format!("line {}: invalid benchmark run.", i + 1)))
+        })
+        .collect()
+}
+
+/// Appends a run to the history of a year.
+pub fn record(year: Year, run: &Run) -> Result<(), Error> {
+    let path = get_path_for_history(year);
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", run.to_json())?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{compare, find_baseline, Baseline, Run};
+    use crate::{day, Day};
+    use std::collections::BTreeMap;
+
+    fn run(commit: &str, release: bool, nanos: &[(u8, u8, u64)]) -> Run {
+        Run {
+            timestamp: 1000,
+            commit: Some(commit.into()),
+            release,
+            timings: nanos
+                .iter()
+                .map(|&(day, part, nanos)| ((Day::new(day).unwrap(), part), nanos))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn round_trips_runs() {
+        let run = run("1a2b3c4", true, &[(1, 1, 74), (16, 2, 130_000)]);
+        assert_eq!(Run::from_json(&run.to_json()), Some(run.clone()));
+
+        let run = Run {
+            commit: None,
+            ..run
+        };
+        assert_eq!(Run::from_json(&run.to_json()), Some(run));
+    }
+
+    #[test]
+    fn finds_baselines() {
+        let runs = vec![
+            run("aaaaaaa", true, &[]),
+            run("bbbbbbb", true, &[]),
+            run("ccccccc", false, &[]),
+        ];
+
+        let commit = |run: Option<&Run>| run.and_then(|run| run.commit.clone());
+        assert_eq!(
+            commit(find_baseline(&runs, &Baseline::Latest, true)),
+            Some("bbbbbbb".into())
+        );
+        assert_eq!(
+            commit(find_baseline(&runs, &Baseline::Latest, false)),
+            Some("ccccccc".into())
+        );
+        assert_eq!(
+            commit(find_baseline(&runs, &Baseline::Commit("aaa".into()), true)),
+            Some("aaaaaaa".into())
+        );
+        assert_eq!(
+            find_baseline(&runs, &Baseline::Commit("ccc".into()), true),
+            None
+        );
+    }
+
+    #[test]
+    fn compares_runs() {
+        let before = run("aaaaaaa", true, &[(1, 1, 100), (16, 1, 1000), (16, 2, 500)]);
+        let after = run("bbbbbbb", true, &[(1, 1, 90), (16, 1, 1500), (17, 1, 10)]);
+
+        let deltas = compare(&before, &after);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].percent, -10.0);
+        assert!(!deltas[0].is_regression(5.0));
+        assert_eq!(deltas[1].day, day!(16));
+        assert_eq!(deltas[1].percent, 50.0);
+        assert!(deltas[1].is_regression(5.0));
+        assert!(!deltas[1].is_regression(60.0));
+    }
+}
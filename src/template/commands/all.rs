@@ -1,51 +1,395 @@
-use std::io;
+use std::{io, process, time::Duration};
 
 use crate::template::{
+    allocator::{format_bytes, AllocStats},
+    benchmark_export::{self, Export},
+    benchmark_history::{self, Baseline, Run},
     readme_benchmarks::{self, Timings},
+    registry,
+    runner::{set_threads, BenchConfig, PartReport, Status, PARSE_STEP},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, Year};
+
+use child_commands::{DayRun, Line, RunOptions};
+
+/// Options of `cargo all`.
+pub struct AllOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub bench_config: BenchConfig,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    /// Run only this part of every day.
+    pub part: Option<u8>,
+    /// Run the solutions linked into this binary rather than spawning their bins.
+    pub in_process: bool,
+    /// Compare the timings with an earlier run. Implies a timed run.
+    pub compare: Option<Baseline>,
+    /// Slowdown in percent above which a part counts as a regression.
+    pub threshold: f64,
+    /// File to export the timings to, as CSV, JSON or SVG by its extension. Implies a timed run.
+    pub export: Option<Export>,
+    /// Size of the thread pool of every solution.
+    pub threads: Option<usize>,
+}
+
+pub fn handle(year: Year, options: AllOptions) {
+    let AllOptions {
+        is_release,
+        is_timed,
+        bench_config,
+        jobs,
+        timeout,
+        part,
+        in_process,
+        compare,
+        threshold,
+        export,
+        threads,
+    } = options;
+
+    let is_timed = is_timed || compare.is_some() || export.is_some();
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut summary: Vec<DaySummary> = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+    if in_process {
+        if registry::solutions().is_empty() {
+            eprintln!(
+                "No solutions are linked into this binary. Build it with `--features in_process`."
+            );
+            process::exit(1);
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        if jobs > 1 || timeout.is_some() {
+            println!("In-process runs are executed sequentially and without a timeout, ignoring `--jobs` and `--timeout`.\n");
+        }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if let Some(threads) = threads {
+            if let Err(e) = set_threads(threads) {
+                eprintln!("Could not configure the thread pool: {e}");
+                process::exit(1);
+            }
+        }
+    } else if let Err(e) = child_commands::build_solutions(year, is_release) {
+        eprintln!("Failed to build solutions: {e:?}");
+        process::exit(1);
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    // in-process solutions are built with the profile of this binary.
+    let is_release = if in_process {
+        !cfg!(debug_assertions)
+    } else {
+        is_release
+    };
+
+    // concurrent runs would skew the timings of each other.
+    let jobs = if is_timed && jobs > 1 {
+        println!("Timed runs are executed sequentially, ignoring `--jobs {jobs}`.\n");
+        1
+    } else {
+        jobs.max(1)
+    };
+
+    let options = RunOptions {
+        year,
+        is_timed,
+        is_release,
+        bench_config,
+        timeout,
+        part,
+        threads,
+    };
+
+    let days: Vec<Day> = all_days().collect();
+
+    let mut collect_results = |day: Day, run: DayRun| {
+        match &run.status {
+            DayStatus::Unsolved if run.reports.is_empty() => println!("Not solved."),
+            DayStatus::Passed | DayStatus::Unsolved | DayStatus::NoInput => {}
+            status => eprintln!("{ANSI_BOLD}✘ {status}{ANSI_RESET}"),
+        }
+
+        if !run.reports.is_empty() {
+            timings.push(child_commands::collect_timings(&run.reports, day));
+        }
+
+        summary.push(DaySummary {
+            day,
+            alloc: total_alloc(&run.reports),
+            status: run.status,
+        });
+    };
+
+    if in_process {
+        for day in days {
+            print_header(day);
+            let run = in_process::run_solution(day, &options, print_line);
+            collect_results(day, run);
+        }
+    } else if jobs == 1 {
+        for day in days {
+            print_header(day);
+            let run = child_commands::run_solution(day, &options, print_line);
+            collect_results(day, run);
         }
-    });
+    } else {
+        child_commands::run_solutions_parallel(&days, jobs, &options, |day, lines, run| {
+            print_header(day);
+            lines.iter().for_each(print_line);
+            collect_results(day, run);
+        });
+    }
+
+    let mut regressions = 0;
 
     if is_timed {
+        let run = Run::new(&timings, is_release);
+
+        if let Some(baseline) = &compare {
+            regressions = compare_with_history(year, &run, baseline, threshold);
+        }
+
+        if let Err(e) = benchmark_history::record(year, &run) {
+            eprintln!("Failed to record benchmark history: {e:?}");
+        }
+
+        if let Some(export) = &export {
+            match benchmark_export::export(export, year, &timings) {
+                Ok(()) => println!("Exported benchmarks to {}.", export.path),
+                Err(e) => eprintln!("Failed to export benchmarks: {e:?}"),
+            }
+        }
+
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+        // debug runs only end up in the README if it has a table for them.
+        match readme_benchmarks::update(year, is_release, &timings, total_millis, part) {
+            Ok(()) => println!("Successfully updated README with benchmarks."),
+            Err(_) if !is_release => {}
+            Err(_) => {
+                eprintln!("Failed to update readme with benchmarks.");
             }
         }
     }
+
+    print_summary(year, &summary);
+
+    if regressions > 0 {
+        eprintln!(
+            "{ANSI_BOLD}✘ {regressions} part(s) got more than {threshold}% slower.{ANSI_RESET}"
+        );
+    }
+
+    if summary.iter().any(|row| row.status.is_failure()) || regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Prints the change of every part relative to the baseline and returns the number of regressions.
+fn compare_with_history(year: Year, run: &Run, baseline: &Baseline, threshold: f64) -> usize {
+    let runs = match benchmark_history::read(year) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e:?}");
+            return 0;
+        }
+    };
+
+    let Some(before) = benchmark_history::find_baseline(&runs, baseline, run.release) else {
+        let profile = if run.release { "release" } else { "debug" };
+        println!("\nNo earlier {profile} run matches the baseline, nothing to compare with.");
+        return 0;
+    };
+
+    println!(
+        "\n{ANSI_BOLD}Comparison with {}{ANSI_RESET}",
+        before.commit.as_deref().unwrap_or("unknown commit")
+    );
+    println!("------");
+    println!("| Day | Part | Before | After | Change");
+    println!("| :-: | :--: | -----: | ----: | -----:");
+
+    let deltas = benchmark_history::compare(before, run);
+
+    for delta in &deltas {
+        let flag = if delta.is_regression(threshold) {
+            " ✘ regression"
+        } else {
+            ""
+        };
+        let part = match delta.part {
+            PARSE_STEP => "parse".into(),
+            part => part.to_string(),
+        };
+        println!(
+            "| {}  | {part:<4} | {:.1?} | {:.1?} | {:+.1}%{flag}",
+            delta.day, delta.before, delta.after, delta.percent
+        );
+    }
+
+    deltas
+        .iter()
+        .filter(|delta| delta.is_regression(threshold))
+        .count()
+}
+
+/// The overall result of running the solution of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayStatus {
+    /// The bin exited successfully and at least one part returned an answer.
+    Passed,
+    /// The bin does not exist yet or no part returned an answer.
+    Unsolved,
+    /// The input file is missing or empty, so the day was skipped.
+    NoInput,
+    /// A part returned an answer that differs from the recorded one.
+    AnswerChanged,
+    /// The bin panicked.
+    Panicked,
+    /// The bin exited with a non-zero status or could not be run.
+    Failed(String),
+    /// The bin did not finish within the timeout and was killed.
+    TimedOut(Duration),
+}
+
+impl DayStatus {
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        !matches!(
+            self,
+            DayStatus::Passed | DayStatus::Unsolved | DayStatus::NoInput
+        )
+    }
+
+    /// The status of a day whose solution ran to completion.
+    fn from_reports(reports: &[PartReport]) -> Self {
+        if let Some((part, error)) = reports.iter().find_map(|report| match &report.status {
+            Status::Error(error) => Some((report.part, error)),
+            _ => None,
+        }) {
+            DayStatus::Failed(format!("part {part} returned an error: {error}"))
+        } else if reports
+            .iter()
+            .any(|report| matches!(report.status, Status::Mismatch(_)))
+        {
+            DayStatus::AnswerChanged
+        } else if reports.iter().all(|report| report.answer.is_none()) {
+            DayStatus::Unsolved
+        } else {
+            DayStatus::Passed
+        }
+    }
+}
+
+impl std::fmt::Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Passed => write!(f, "passed"),
+            DayStatus::Unsolved => write!(f, "unsolved"),
+            DayStatus::NoInput => write!(f, "no input"),
+            DayStatus::AnswerChanged => write!(f, "answer changed"),
+            DayStatus::Panicked => write!(f, "panicked"),
+            DayStatus::Failed(reason) => write!(f, "failed: {reason}"),
+            DayStatus::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+        }
+    }
+}
+
+fn print_header(day: Day) {
+    if day > 1 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_line(line: &Line) {
+    match line {
+        Line::Report(report) => report.print(),
+        Line::Stdout(line) => println!("{line}"),
+        Line::Stderr(line) => eprintln!("{line}"),
+    }
+}
+
+/// A row of the summary that is printed after all days ran.
+struct DaySummary {
+    day: Day,
+    status: DayStatus,
+    /// Allocations of all parts of the day, if allocations are counted.
+    alloc: Option<AllocStats>,
+}
+
+/// Sums the allocations of the parts of a day. The peak is the highest peak of any part.
+fn total_alloc(reports: &[PartReport]) -> Option<AllocStats> {
+    reports
+        .iter()
+        .filter_map(|report| report.stats.alloc)
+        .reduce(|a, b| AllocStats {
+            allocations: a.allocations + b.allocations,
+            bytes: a.bytes + b.bytes,
+            peak_bytes: a.peak_bytes.max(b.peak_bytes),
+        })
+}
+
+fn print_summary(year: Year, summary: &[DaySummary]) {
+    let has_alloc = summary.iter().any(|row| row.alloc.is_some());
+
+    println!("\n{ANSI_BOLD}Summary {year}{ANSI_RESET}");
+    println!("------");
+
+    if has_alloc {
+        println!("| Day | Allocations | Allocated | Peak | Status");
+        println!("| :-: | ----------: | --------: | ---: | :-----");
+    } else {
+        println!("| Day | Status");
+        println!("| :-: | :-----");
+    }
+
+    for DaySummary { day, status, alloc } in summary {
+        let symbol = match status {
+            DayStatus::Passed => "✔",
+            DayStatus::Unsolved | DayStatus::NoInput => "-",
+            _ => "✘",
+        };
+
+        if has_alloc {
+            let (allocations, bytes, peak) = alloc.map_or_else(
+                || ("-".into(), "-".into(), "-".into()),
+                |alloc| {
+                    (
+                        alloc.allocations.to_string(),
+                        format_bytes(alloc.bytes),
+                        format_bytes(alloc.peak_bytes),
+                    )
+                },
+            );
+            println!("| {day}  | {allocations:>11} | {bytes:>9} | {peak:>4} | {symbol} {status}");
+        } else {
+            println!("| {day}  | {symbol} {status}");
+        }
+    }
+
+    let count = |f: fn(&DayStatus) -> bool| summary.iter().filter(|row| f(&row.status)).count();
+
+    println!(
+        "\n{ANSI_BOLD}Passed:{ANSI_RESET} {}, {ANSI_BOLD}Failed:{ANSI_RESET} {}, {ANSI_BOLD}Timed out:{ANSI_RESET} {}, {ANSI_BOLD}Unsolved:{ANSI_RESET} {}, {ANSI_BOLD}No input:{ANSI_RESET} {}",
+        count(|s| *s == DayStatus::Passed),
+        count(|s| s.is_failure() && !matches!(s, DayStatus::TimedOut(_))),
+        count(|s| matches!(s, DayStatus::TimedOut(_))),
+        count(|s| *s == DayStatus::Unsolved),
+        count(|s| *s == DayStatus::NoInput),
+    );
 }
 
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BadExitStatus(process::ExitStatus),
     Parser(String),
     IO(io::Error),
 }
@@ -57,46 +401,258 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{year}-{day}.rs")
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+/// Runs the solutions that are linked into this binary, see [`registry`].
+mod in_process {
+    use super::child_commands::{DayRun, Line, RunOptions};
+    use super::DayStatus;
+    use crate::template::{
+        has_input, registry,
+        runner::{report_parse, report_part},
+        try_read_file,
+    };
     use crate::Day;
     use std::{
+        any::Any,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    /// Run the registered solution for a given day, passing each report to `on_line` as it is produced.
+    /// A panic of the solution is reported through the returned status.
+    pub fn run_solution(day: Day, options: &RunOptions, mut on_line: impl FnMut(&Line)) -> DayRun {
+        let Some(solution) = registry::find(options.year, day) else {
+            return DayRun {
+                reports: vec![],
+                status: DayStatus::Unsolved,
+            };
+        };
+
+        let input = match try_read_file("inputs", options.year, day) {
+            Ok(input) => input,
+            Err(e) => {
+                on_line(&Line::Stderr(e));
+                return DayRun {
+                    reports: vec![],
+                    status: if has_input(options.year, day) {
+                        DayStatus::Failed("could not read input".into())
+                    } else {
+                        DayStatus::NoInput
+                    },
+                };
+            }
+        };
+
+        let bench_config = options.is_timed.then_some(&options.bench_config);
+        let mut reports = vec![];
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let parsed = if solution.has_parse_step() {
+                let (parsed, report) =
+                    report_parse(|input| solution.parse(input), &input, day, bench_config);
+                on_line(&Line::Report(report.clone()));
+                reports.push(report);
+                parsed
+            } else {
+                solution.parse(&input)
+            };
+
+            for part in [1, 2] {
+                if options.part.is_some_and(|only| only != part) {
+                    continue;
+                }
+
+                let report = report_part(
+                    |parsed: &dyn Any| solution.part(part, parsed),
+                    &*parsed,
+                    options.year,
+                    day,
+                    part,
+                    bench_config,
+                );
+
+                on_line(&Line::Report(report.clone()));
+                reports.push(report);
+            }
+        }));
+
+        let status = match result {
+            Ok(()) => DayStatus::from_reports(&reports),
+            Err(_) => DayStatus::Panicked,
+        };
+
+        DayRun { reports, status }
+    }
+}
+
+/// All solutions live in isolated binaries.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their results.
+pub(crate) mod child_commands {
+    use super::{get_path_for_bin, DayStatus, Error};
+    use crate::template::allocator;
+    use crate::template::runner::{BenchConfig, PartReport, NO_INPUT_EXIT_CODE, PARSE_STEP};
+    use crate::{all_days, Day, Year};
+    use std::{
+        collections::BTreeMap,
+        env,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc::{self, RecvTimeoutError},
+        },
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Exit code of a Rust process that panicked.
+    const PANIC_EXIT_CODE: i32 = 101;
+
+    /// A line of output of a solution binary.
+    #[derive(Clone)]
+    pub enum Line {
+        Report(PartReport),
+        Stdout(String),
+        Stderr(String),
+    }
+
+    /// Options that apply to every invocation of a solution bin.
+    pub struct RunOptions {
+        pub year: Year,
+        pub is_timed: bool,
+        pub is_release: bool,
+        pub bench_config: BenchConfig,
+        pub timeout: Option<Duration>,
+        /// Run only this part of every day.
+        pub part: Option<u8>,
+        /// Size of the thread pool of every solution.
+        pub threads: Option<usize>,
+    }
+
+    /// The reports and overall status of a solution bin invocation.
+    pub struct DayRun {
+        pub reports: Vec<PartReport>,
+        pub status: DayStatus,
+    }
+
+    /// Build the solution bins of a year once, so they can be invoked without going through
+    /// `cargo run`. The output of cargo is only shown if the build fails.
+    pub fn build_solutions(year: Year, is_release: bool) -> Result<(), Error> {
+        let bins: Vec<String> = all_days()
+            .filter(|&day| Path::new(&get_path_for_bin(year, day)).exists())
+            .map(|day| format!("{year}-{day}"))
+            .collect();
+
+        if bins.is_empty() {
+            return Ok(());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["build", "--quiet"];
+
+        for bin in &bins {
+            args.extend(["--bin", bin]);
+        }
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        args.extend(allocator::get_feature_args());
+
+        let output = Command::new("cargo").args(&args).output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            Err(Error::BadExitStatus(output.status))
+        }
+    }
+
+    /// Path of the compiled binary for a given day.
+    fn get_path_for_exe(year: Year, day: Day, is_release: bool) -> PathBuf {
+        let target_dir =
+            env::var_os("CARGO_TARGET_DIR").map_or_else(|| "target".into(), PathBuf::from);
+        let profile = if is_release { "release" } else { "debug" };
+        target_dir
+            .join(profile)
+            .join(format!("{year}-{day}{}", env::consts::EXE_SUFFIX))
+    }
+
+    /// Run the solution bin for a given day, passing each line of output to `on_line` as it arrives.
+    /// Failures of the bin are reported through the returned status rather than as an error.
+    pub fn run_solution(day: Day, options: &RunOptions, mut on_line: impl FnMut(&Line)) -> DayRun {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(options.year, day)).exists() {
+            return DayRun {
+                reports: vec![],
+                status: DayStatus::Unsolved,
+            };
+        }
+
+        let mut reports = vec![];
+
+        let status = match spawn_and_collect(day, options, |line| {
+            on_line(&line);
+
+            if let Line::Report(report) = line {
+                reports.push(report);
+            }
+        }) {
+            Ok(None) => DayStatus::TimedOut(options.timeout.unwrap_or_default()),
+            Ok(Some(exit_status)) => get_day_status(exit_status, &reports),
+            Err(e) => DayStatus::Failed(format!("{e:?}")),
+        };
+
+        DayRun { reports, status }
+    }
+
+    fn get_day_status(exit_status: ExitStatus, reports: &[PartReport]) -> DayStatus {
+        match exit_status.code() {
+            Some(0) => {}
+            Some(PANIC_EXIT_CODE) => return DayStatus::Panicked,
+            Some(NO_INPUT_EXIT_CODE) => return DayStatus::NoInput,
+            Some(code) => return DayStatus::Failed(format!("exit code {code}")),
+            None => return DayStatus::Failed("terminated by signal".into()),
+        }
+
+        DayStatus::from_reports(reports)
+    }
+
+    /// Spawns the bin and forwards its output until it exits.
+    /// Returns [`None`] if the bin had to be killed because it exceeded the timeout.
+    fn spawn_and_collect(
+        day: Day,
+        options: &RunOptions,
+        mut on_line: impl FnMut(Line),
+    ) -> Result<Option<ExitStatus>, Error> {
+        // request machine-readable results and mirror `--time` and bench flags to child invocations.
+        let mut args: Vec<String> = vec!["--format".into(), "json".into()];
+
+        if let Some(part) = options.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        if let Some(threads) = options.threads {
+            args.push("--threads".into());
+            args.push(threads.to_string());
+        }
+
+        if options.is_timed {
+            args.push("--time".into());
+            args.extend(options.bench_config.to_args());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // both streams are read on separate threads and merged into one channel.
+
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(get_path_for_exe(options.year, day, options.is_release))
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -105,150 +661,206 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let (tx, rx) = mpsc::channel();
+        let stderr_tx = tx.clone();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                // anything that is not a report was printed by the solution itself.
+                let line = PartReport::from_json(&line).map_or(Line::Stdout(line), Line::Report);
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        thread::spawn(move || {
+            for line in stderr.lines().map_while(Result::ok) {
+                if stderr_tx.send(Line::Stderr(line)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => on_line(line),
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => return kill(&mut cmd),
+            }
         }
 
-        thread.join().unwrap();
+        // the output streams may be closed before the process exits.
+        while let Some(deadline) = deadline {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if Instant::now() >= deadline {
+                return kill(&mut cmd);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        Ok(Some(cmd.wait()?))
+    }
+
+    fn kill(cmd: &mut Child) -> Result<Option<ExitStatus>, Error> {
+        cmd.kill()?;
         cmd.wait()?;
+        Ok(None)
+    }
+
+    /// Run the solution bins of multiple days on `jobs` threads.
+    /// The output of each day is buffered and passed to `on_day` in order of `days`.
+    pub fn run_solutions_parallel(
+        days: &[Day],
+        jobs: usize,
+        options: &RunOptions,
+        mut on_day: impl FnMut(Day, Vec<Line>, DayRun),
+    ) {
+        let next_index = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                let tx = tx.clone();
+                let next_index = &next_index;
+
+                scope.spawn(move || loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(index) else {
+                        break;
+                    };
+
+                    let mut lines = vec![];
+                    let run = run_solution(day, options, |line| lines.push(line.clone()));
+
+                    if tx.send((index, lines, run)).is_err() {
+                        break;
+                    }
+                });
+            }
+
+            drop(tx);
 
-        Ok(output)
+            let mut pending = BTreeMap::new();
+            let mut next_to_print = 0;
+
+            for (index, lines, run) in rx {
+                pending.insert(index, (lines, run));
+
+                while let Some((lines, run)) = pending.remove(&next_to_print) {
+                    on_day(days[next_to_print], lines, run);
+                    next_to_print += 1;
+                }
+            }
+        });
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    /// Collects the timings of all solved parts of a day.
+    pub fn collect_timings(reports: &[PartReport], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            status_1: None,
+            status_2: None,
             total_nanos: 0_f64,
         };
 
-        output
+        for report in reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|report| report.answer.is_some() || report.is_parse_step())
+        {
+            match report.part {
+                PARSE_STEP => timings.parse = Some(report.stats),
+                1 => {
+                    timings.part_1 = Some(report.stats);
+                    timings.status_1 = Some(report.status.clone());
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                2 => {
+                    timings.part_2 = Some(report.stats);
+                    timings.status_2 = Some(report.status.clone());
                 }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = report.stats.median.as_nanos() as f64;
+            timings.total_nanos += nanos;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
-        use super::parse_exec_time;
-
+        use super::collect_timings;
         use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        use crate::template::runner::{BenchStats, PartReport, Status};
+        use std::time::Duration;
+
+        fn report(part: u8, answer: Option<&str>, nanos: u64) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                answer: answer.map(String::from),
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                stats: BenchStats {
+                    samples: 100,
+                    ..BenchStats::single(Duration::from_nanos(nanos))
+                },
+            }
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+        fn test_collect_timings() {
+            let res = collect_timings(
+                &[report(1, Some("0"), 74), report(2, Some("10"), 74_130_000)],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(74));
+            assert_eq!(res.part_2.unwrap().samples, 100);
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            let res = collect_timings(&[report(1, None, 74), report(2, None, 74)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_reports_survive_round_trip() {
+            let mut original = report(2, Some("@ @ ( ) ms (2s @ 5 samples)\n#"), 2_000_000_000);
+            original.status = Status::Mismatch("42".into());
+            let parsed = PartReport::from_json(&original.to_json()).unwrap();
+            assert_eq!(parsed, original);
+
+            let mut original = report(1, None, 1_000);
+            original.status = Status::Error("invalid direction".into());
+            let parsed = PartReport::from_json(&original.to_json()).unwrap();
+            assert_eq!(parsed, original);
+            assert_eq!(
+                PartReport::from_json("Part 1: 0 (74.13ns @ 100000 samples)"),
+                None
+            );
+        }
     }
 }
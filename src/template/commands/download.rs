@@ -1,15 +1,54 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use crate::template::aoc_client::AocClient;
+use crate::template::puzzle::{load_html, populate_example};
+use crate::{Day, Year};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+#[must_use]
+pub fn get_input_path(year: Year, day: Day) -> String {
+    format!("data/{year}/inputs/{day}.txt")
+}
+
+pub fn handle(year: Year, day: Day, example: bool, html_path: Option<&str>) {
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up Advent of Code client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input = match client.download_input(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to download input: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = get_input_path(year, day);
+
+    if let Err(e) = fs::create_dir_all(format!("data/{year}/inputs")) {
+        eprintln!("Failed to create input directory: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = fs::write(&input_path, input) {
+        eprintln!("Failed to write input file: {e}");
         process::exit(1);
-    };
+    }
+
+    if example || html_path.is_some() {
+        let html = load_html(year, day, html_path);
+        match populate_example(year, day, &html) {
+            Ok(true) => println!("Wrote example input to \"data/{year}/examples/{day}.txt\"."),
+            Ok(false) => println!("Example file already has content or the page has no example."),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
 }
@@ -1,4 +1,7 @@
 pub mod all;
 pub mod download;
+pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod watch;
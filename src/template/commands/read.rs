@@ -0,0 +1,25 @@
+use std::{fs, process};
+
+use crate::template::puzzle::{get_path_for_puzzle, load_html, to_markdown};
+use crate::{Day, Year};
+
+pub fn handle(year: Year, day: Day, html_path: Option<&str>) {
+    let html = load_html(year, day, html_path);
+    let puzzle_path = get_path_for_puzzle(year, day);
+
+    if let Err(e) = fs::create_dir_all(format!("data/{year}/puzzles")) {
+        eprintln!("Failed to create puzzle directory: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(&puzzle_path, to_markdown(&html)) {
+        eprintln!("Failed to write puzzle description: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote puzzle description to \"{}\".",
+        &puzzle_path
+    );
+}
@@ -1,38 +1,49 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::puzzle::{extract_example_answers, load_html, populate_example};
+use crate::template::{count_examples, get_example_suffix};
+use crate::{Day, Year};
 
+/// Directory of user-defined module templates, selected with `--template <name>`.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Used when no template is selected. User templates use the same placeholders:
+///
+/// - `YEAR_NUMBER` and `DAY_NUMBER`: the year and the day, e.g. `2023` and `1`.
+/// - `ANSWER_TYPE`: `u32`, widened to `u64`, `i64` or `String` if an example answer does not fit.
+/// - `EXAMPLE_ANSWER_ONE` and `EXAMPLE_ANSWER_TWO`: the expected example answers, e.g. `Some(142)` or `None`.
+/// - `TESTS`: a test for every example file.
+///
+/// A template can pin the answer type with a first line like `// answer: String`, which is not copied.
+/// A pinned type is still widened if an example answer does not fit it.
 const MODULE_TEMPLATE: &str = r#"use rayon::prelude::*;
-advent_of_code::solution!(DAY_NUMBER);
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<ANSWER_TYPE> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<ANSWER_TYPE> {
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+TESTS}
+"#;
 
+const TEST_TEMPLATE: &str = r#"
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    fn test_PART_NAMESUFFIX() {
+        let result = PART_NAME(INPUT);
+        assert_eq!(result, EXPECTED);
     }
-}
 "#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -40,13 +51,241 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// The return type of the parts of a scaffolded module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AnswerType {
+    U32,
+    U64,
+    I64,
+    String,
+}
+
+impl AnswerType {
+    /// The narrowest type that holds every example answer, `u32` if there are none.
+    fn infer(answers: &[Option<String>; 2]) -> Self {
+        answers
+            .iter()
+            .flatten()
+            .map(|answer| {
+                if answer.parse::<u32>().is_ok() {
+                    Self::U32
+                } else if answer.parse::<u64>().is_ok() {
+                    Self::U64
+                } else if answer.parse::<i64>().is_ok() {
+                    Self::I64
+                } else {
+                    Self::String
+                }
+            })
+            .max()
+            .unwrap_or(Self::U32)
+    }
+
+    /// The pinned type, widened if an example answer does not fit it, e.g. to `i64` for a negative
+    /// answer. Without a pin, the type is inferred.
+    fn resolve(pinned: Option<Self>, answers: &[Option<String>; 2]) -> Self {
+        let inferred = Self::infer(answers);
+        pinned.map_or(inferred, |pinned| pinned.max(inferred))
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::U32, Self::U64, Self::I64, Self::String]
+            .into_iter()
+            .find(|answer_type| answer_type.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I64 => "i64",
+            Self::String => "String",
+        }
+    }
+}
+
+/// Formats an example answer as the expected value of a generated test.
+fn format_expected(answer: Option<&String>, answer_type: AnswerType) -> String {
+    match (answer, answer_type) {
+        (Some(answer), AnswerType::String) => format!("Some({answer:?}.to_string())"),
+        (Some(answer), _) if answer.parse::<i64>().is_ok() || answer.parse::<u64>().is_ok() => {
+            format!("Some({answer})")
+        }
+        _ => "None".into(),
+    }
+}
+
+/// Reads `templates/<name>.rs`, or returns the built-in template if no name is given.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let path = format!("{TEMPLATES_DIR}/{name}.rs");
+    fs::read_to_string(&path).map_err(|e| {
+        let mut available: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                file_name.strip_suffix(".rs").map(String::from)
+            })
+            .collect();
+        available.sort();
+
+        match available.len() {
+            0 => format!("Failed to read template \"{path}\": {e}"),
+            _ => format!(
+                "Failed to read template \"{path}\": {e}. Available templates: {}",
+                available.join(", ")
+            ),
+        }
+    })
+}
+
+/// Splits off the `// answer: <type>` line that pins the answer type of a template, if there is one.
+fn split_answer_type(template: &str) -> Result<(Option<AnswerType>, &str), String> {
+    let Some(rest) = template.strip_prefix("// answer:") else {
+        return Ok((None, template));
+    };
+
+    let (name, body) = rest.split_once('\n').unwrap_or((rest, ""));
+    match AnswerType::from_name(name.trim()) {
+        Some(answer_type) => Ok((Some(answer_type), body)),
+        None => Err(format!(
+            "Unknown answer type \"{}\", expected u32, u64, i64 or String.",
+            name.trim()
+        )),
+    }
+}
+
+/// Fills in the placeholders of a module template.
+fn render_template(
+    template: &str,
+    year: Year,
+    day: Day,
+    answers: &[Option<String>; 2],
+    answer_type: AnswerType,
+    tests: &str,
+) -> String {
+    template
+        .replace("YEAR_NUMBER", &year.to_string())
+        .replace("DAY_NUMBER", &day.into_inner().to_string())
+        .replace("ANSWER_TYPE", answer_type.name())
+        .replace(
+            "EXAMPLE_ANSWER_ONE",
+            &format_expected(answers[0].as_ref(), answer_type),
+        )
+        .replace(
+            "EXAMPLE_ANSWER_TWO",
+            &format_expected(answers[1].as_ref(), answer_type),
+        )
+        .replace("TESTS", tests)
+}
+
+/// The parse function of a template's `solution!(.., parse = <function>)`, if it has one.
+fn find_parse_step(template: &str) -> Option<&str> {
+    let (_, rest) = template.split_once("parse = ")?;
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|name| !name.is_empty())
+}
+
+/// Generates a test for every example file of each part, or a single test per part if there are none yet.
+/// Only the first example of a part is checked against the answer extracted from the puzzle page.
+/// If the module has a parse step, the example is parsed before it is passed to a part.
+fn build_tests(
+    year: Year,
+    day: Day,
+    answers: &[Option<String>; 2],
+    answer_type: AnswerType,
+    parse: Option<&str>,
+) -> String {
+    let mut tests = String::new();
+
+    for (part, name) in [(1, "part_one"), (2, "part_two")] {
+        let count = count_examples(year, day, part).max(1);
+
+        for index in 0..count {
+            let suffix = match index {
+                0 => String::new(),
+                _ => format!("_{}", get_example_suffix(index)),
+            };
+
+            let expected = match index {
+                0 => format_expected(answers[usize::from(part) - 1].as_ref(), answer_type),
+                _ => format_expected(None, answer_type),
+            };
+
+            let example = "advent_of_code::template::read_example(YEAR, DAY, PART_NUMBER, INDEX)";
+            let input = match parse {
+                Some(parse) => format!("&{parse}(&{example})"),
+                None => format!("&{example}"),
+            };
+
+            tests.push_str(
+                &TEST_TEMPLATE
+                    .replace("PART_NAME", name)
+                    .replace("INPUT", &input)
+                    .replace("SUFFIX", &suffix)
+                    .replace("PART_NUMBER", &part.to_string())
+                    .replace("INDEX", &index.to_string())
+                    .replace("EXPECTED", &expected),
+            );
+        }
+    }
+
+    tests
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(
+    year: Year,
+    day: Day,
+    example: bool,
+    html_path: Option<&str>,
+    template: Option<&str>,
+) {
+    let input_path = format!("data/{year}/inputs/{day}.txt");
+    let example_path = format!("data/{year}/examples/{day}.txt");
+    let module_path = format!("src/bin/{year}-{day}.rs");
+
+    // checked before the puzzle is fetched, so a rejected scaffold leaves nothing behind.
+    if Path::new(&module_path).exists() {
+        eprintln!("Failed to create module file: \"{module_path}\" already exists.");
+        process::exit(1);
+    }
+
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let (answer_type, template) = match split_answer_type(&template) {
+        Ok(split) => split,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let html = (example || html_path.is_some()).then(|| load_html(year, day, html_path));
+    let answers = html
+        .as_deref()
+        .map(extract_example_answers)
+        .unwrap_or_default();
+    let answer_type = AnswerType::resolve(answer_type, &answers);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -56,11 +295,10 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let tests = build_tests(year, day, &answers, answer_type, find_parse_step(template));
+    let module = render_template(template, year, day, &answers, answer_type, &tests);
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -70,6 +308,13 @@ pub fn handle(day: Day) {
         }
     }
 
+    for folder in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(format!("data/{year}/{folder}")) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -90,6 +335,128 @@ pub fn handle(day: Day) {
         }
     }
 
+    if let Some(html) = &html {
+        match populate_example(year, day, html) {
+            Ok(true) => println!("Wrote example input to \"{}\"", &example_path),
+            Ok(false) => println!("No example input written to \"{}\"", &example_path),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        day, year
+    );
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find_parse_step, format_expected, render_template, split_answer_type, AnswerType};
+    use crate::{day, year};
+
+    #[test]
+    fn formats_expected_answers() {
+        assert_eq!(
+            format_expected(Some(&"142".into()), AnswerType::U32),
+            "Some(142)"
+        );
+        assert_eq!(
+            format_expected(Some(&"abc".into()), AnswerType::String),
+            "Some(\"abc\".to_string())"
+        );
+        assert_eq!(
+            format_expected(Some(&"abc".into()), AnswerType::U32),
+            "None"
+        );
+        assert_eq!(
+            format_expected(Some(&"142".into()), AnswerType::String),
+            "Some(\"142\".to_string())"
+        );
+        assert_eq!(format_expected(None, AnswerType::U64), "None");
+    }
+
+    #[test]
+    fn infers_answer_types() {
+        let infer = |one: Option<&str>, two: Option<&str>| {
+            AnswerType::infer(&[one.map(String::from), two.map(String::from)])
+        };
+
+        assert_eq!(infer(None, None), AnswerType::U32);
+        assert_eq!(infer(Some("142"), None), AnswerType::U32);
+        assert_eq!(infer(Some("142"), Some("5000000000")), AnswerType::U64);
+        assert_eq!(infer(Some("-3"), Some("42")), AnswerType::I64);
+        assert_eq!(infer(Some("abc"), Some("42")), AnswerType::String);
+    }
+
+    #[test]
+    fn renders_templates() {
+        let template = "solution!(YEAR_NUMBER, DAY_NUMBER);\n\
+            fn part_one(input: &str) -> Option<ANSWER_TYPE> {}\n\
+            assert_eq!(result, EXAMPLE_ANSWER_ONE);\n\
+            assert_eq!(result, EXAMPLE_ANSWER_TWO);\n\
+            TESTS";
+        let answers = [Some("CMZ".to_string()), None];
+        let answer_type = AnswerType::infer(&answers);
+
+        assert_eq!(
+            render_template(
+                template,
+                year!(2022),
+                day!(5),
+                &answers,
+                answer_type,
+                "// tests"
+            ),
+            "solution!(2022, 5);\n\
+            fn part_one(input: &str) -> Option<String> {}\n\
+            assert_eq!(result, Some(\"CMZ\".to_string()));\n\
+            assert_eq!(result, None);\n\
+            // tests"
+        );
+    }
+
+    #[test]
+    fn pins_answer_types() {
+        assert_eq!(
+            split_answer_type("// answer: String\nfn main() {}\n"),
+            Ok((Some(AnswerType::String), "fn main() {}\n"))
+        );
+        assert_eq!(
+            split_answer_type("fn main() {}\n"),
+            Ok((None, "fn main() {}\n"))
+        );
+        assert!(split_answer_type("// answer: f64\n").is_err());
+    }
+
+    #[test]
+    fn widens_pinned_answer_types() {
+        let answers = |one: &str| [Some(one.to_string()), None];
+
+        assert_eq!(
+            AnswerType::resolve(Some(AnswerType::U64), &answers("142")),
+            AnswerType::U64
+        );
+        assert_eq!(
+            AnswerType::resolve(Some(AnswerType::U32), &answers("-3")),
+            AnswerType::I64
+        );
+        assert_eq!(
+            AnswerType::resolve(Some(AnswerType::String), &answers("-3")),
+            AnswerType::String
+        );
+        assert_eq!(AnswerType::resolve(None, &answers("-3")), AnswerType::I64);
+    }
+
+    #[test]
+    fn finds_parse_steps() {
+        assert_eq!(
+            find_parse_step("solution!(YEAR_NUMBER, DAY_NUMBER, parse = parse_grid);"),
+            Some("parse_grid")
+        );
+        assert_eq!(find_parse_step("solution!(YEAR_NUMBER, DAY_NUMBER);"), None);
+    }
 }
@@ -1,25 +1,97 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::allocator::get_feature_args;
+use crate::template::runner::{BenchConfig, OutputFormat};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options of `cargo solve` that are forwarded to the solution bin.
+pub struct SolveOptions {
+    pub release: bool,
+    pub time: bool,
+    pub submit_part: Option<u8>,
+    /// Run only this part.
+    pub part: Option<u8>,
+    /// Path of an input to run instead of `data/YYYY/inputs/NN.txt`, `-` for stdin.
+    pub input: Option<String>,
+    pub bench_config: BenchConfig,
+    pub format: OutputFormat,
+    /// Size of the thread pool of the solution.
+    pub threads: Option<usize>,
+    /// Bench every part with 1, 2, 4 and all threads.
+    pub scaling: bool,
+}
+
+pub fn handle(year: Year, day: Day, options: SolveOptions) {
+    let SolveOptions {
+        release,
+        time,
+        submit_part,
+        part,
+        input,
+        bench_config,
+        format,
+        threads,
+        scaling,
+    } = options;
+
+    if let (Some(submit_part), Some(part)) = (submit_part, part) {
+        if submit_part != part {
+            eprintln!("Cannot submit part {submit_part} when only running part {part}.");
+            process::exit(1);
+        }
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        format!("{year}-{day}"),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    cmd_args.extend(get_feature_args().iter().map(ToString::to_string));
     cmd_args.push("--".to_string());
 
+    if format != OutputFormat::default() {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(threads) = threads {
+        cmd_args.push("--threads".to_string());
+        cmd_args.push(threads.to_string());
+    }
+
+    if scaling {
+        cmd_args.push("--scaling".to_string());
+    }
+
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    // `--scaling` benches as well.
+    if time || scaling {
+        cmd_args.extend(bench_config.to_args());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
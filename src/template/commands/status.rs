@@ -0,0 +1,475 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Read},
+    path::Path,
+    process::{self, Command},
+    time::Duration,
+};
+
+use crate::template::{
+    answers,
+    benchmark_history::{self, Run},
+    commands::all::{
+        child_commands::{self, RunOptions},
+        get_path_for_bin, DayStatus,
+    },
+    get_example_file_name, has_input,
+    json::ObjectWriter,
+    runner::{BenchConfig, OutputFormat, Status, PARSE_STEP},
+    submissions::{self, History, Verdict},
+    ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+use crate::{all_days, Day, Year};
+
+/// Time after which a solution bin is killed, unless `--timeout` is passed.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options of `cargo status`.
+pub struct StatusOptions {
+    pub is_release: bool,
+    pub format: OutputFormat,
+    /// Time after which a solution bin is killed.
+    pub timeout: Duration,
+}
+
+/// Everything that is known about a day.
+struct DayState {
+    day: Day,
+    has_bin: bool,
+    has_input: bool,
+    has_example: bool,
+    /// Whether the tests of the bin pass, [`None`] if there is no bin.
+    tests: Option<bool>,
+    /// The outcome of running the bin, [`None`] if it was not run.
+    run: Option<DayStatus>,
+    parts: [PartState; 2],
+    /// The time of the day in the latest benchmark run that includes it.
+    timing: Option<Duration>,
+}
+
+struct PartState {
+    /// The status of the part, [`None`] if it did not run.
+    status: Option<Status>,
+    /// Whether an answer is recorded for the part.
+    recorded: bool,
+    /// The verdict of the part's submissions, see [`get_verdict`].
+    verdict: Option<Verdict>,
+}
+
+pub fn handle(year: Year, options: StatusOptions) {
+    let StatusOptions {
+        is_release,
+        format,
+        timeout,
+    } = options;
+
+    if let Err(e) = child_commands::build_solutions(year, is_release) {
+        eprintln!("Failed to build solutions: {e:?}");
+        process::exit(1);
+    }
+
+    let runs = benchmark_history::read(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e:?}");
+        vec![]
+    });
+
+    let run_options = RunOptions {
+        year,
+        is_timed: false,
+        is_release,
+        bench_config: BenchConfig::default(),
+        timeout: Some(timeout),
+        part: None,
+        threads: None,
+    };
+
+    if format == OutputFormat::Human {
+        eprint!("Running tests...");
+    }
+
+    let tests = run_tests(year, is_release);
+
+    let states: Vec<DayState> = all_days()
+        .map(|day| {
+            if format == OutputFormat::Human {
+                eprint!("\rChecking day {day}...");
+            }
+            get_state(year, day, &run_options, &runs, &tests)
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Human => {
+            // clear the progress line.
+            eprint!("\r{:20}\r", "");
+            print_table(year, &states);
+        }
+        OutputFormat::Json => states
+            .iter()
+            .for_each(|state| println!("{}", to_json(state))),
+    }
+}
+
+fn get_state(
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+    runs: &[Run],
+    tests: &HashMap<Day, bool>,
+) -> DayState {
+    let has_bin = Path::new(&get_path_for_bin(year, day)).exists();
+    let has_input = has_input(year, day);
+    let has_example = [1, 2].iter().all(|&part| {
+        let path = format!(
+            "data/{year}/examples/{}",
+            get_example_file_name(day, part, 0)
+        );
+        if Path::new(&path).exists() {
+            is_non_empty(&path)
+        } else {
+            is_non_empty(&format!("data/{year}/examples/{day}.txt"))
+        }
+    });
+
+    // a bin without a result did not get to run its tests, e.g. as they failed to compile.
+    let tests = has_bin.then(|| tests.get(&day).copied().unwrap_or(false));
+
+    // a bin without an input panics, which says nothing about the solution.
+    let run = (has_bin && has_input).then(|| child_commands::run_solution(day, options, |_| {}));
+
+    let answers = answers::read(year, day).unwrap_or_default();
+    let history = submissions::read(year, day).unwrap_or_default();
+
+    let parts = [1, 2].map(|part| PartState {
+        status: run.as_ref().and_then(|run| {
+            run.reports
+                .iter()
+                .find(|report| report.part == part)
+                .map(|report| report.status.clone())
+        }),
+        recorded: answers.get(part).is_some(),
+        verdict: get_verdict(&history, part),
+    });
+
+    DayState {
+        day,
+        has_bin,
+        has_input,
+        has_example,
+        tests,
+        run: run.map(|run| run.status),
+        parts,
+        timing: find_timing(runs, day),
+    }
+}
+
+fn is_non_empty(path: &str) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| !content.trim().is_empty())
+}
+
+/// Runs the example tests of all bins at once and returns whether they pass for each day.
+/// The `real_input` tests are skipped, the part columns already compare the answers with the
+/// recorded ones.
+fn run_tests(year: Year, is_release: bool) -> HashMap<Day, bool> {
+    let mut args = vec!["test", "--bins", "--no-fail-fast"];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    args.extend(["--", "--skip", "real_input"]);
+
+    match read_output(&args) {
+        Ok(output) => parse_test_results(year, &output),
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+/// Runs cargo with stdout and stderr going through one pipe. Cargo names the bin it tests on
+/// stderr, while the test results end up on stdout, so only a shared pipe keeps them in order.
+fn read_output(args: &[&str]) -> Result<String, io::Error> {
+    let (mut reader, writer) = io::pipe()?;
+
+    // the command is dropped right away, so that the pipe closes when cargo exits.
+    let mut child = Command::new("cargo")
+        .args(args)
+        .stdout(writer.try_clone()?)
+        .stderr(writer)
+        .spawn()?;
+
+    let mut output = String::new();
+    reader.read_to_string(&mut output)?;
+    child.wait()?;
+
+    Ok(output)
+}
+
+/// Pairs the `Running unittests src/bin/YYYY-DD.rs` lines of `cargo test` with the result that
+/// follows them.
+fn parse_test_results(year: Year, output: &str) -> HashMap<Day, bool> {
+    let prefix = format!("src/bin/{year}-");
+    let mut results = HashMap::new();
+    let mut day = None;
+
+    for line in output.lines().map(str::trim) {
+        if let Some(target) = line.strip_prefix("Running unittests ") {
+            day = target
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.get(..2))
+                .and_then(|day| day.parse::<Day>().ok());
+        } else if let Some(result) = line.strip_prefix("test result: ") {
+            if let Some(day) = day.take() {
+                results.insert(day, result.starts_with("ok"));
+            }
+        }
+    }
+
+    results
+}
+
+/// The verdict of the correct submission of a part, or of its latest submission if none was correct.
+fn get_verdict(history: &History, part: u8) -> Option<Verdict> {
+    let mut for_part = history.submissions.iter().filter(|s| s.part == part);
+
+    if for_part.clone().any(|s| s.verdict == Verdict::Correct) {
+        return Some(Verdict::Correct);
+    }
+
+    for_part.next_back().map(|s| s.verdict)
+}
+
+/// The time of a day in the latest run that includes it, preferring release runs over debug runs.
+fn find_timing(runs: &[Run], day: Day) -> Option<Duration> {
+    [true, false].into_iter().find_map(|release| {
+        runs.iter()
+            .rev()
+            .filter(|run| run.release == release)
+            .find_map(|run| {
+                let nanos: Vec<u64> = run
+                    .timings
+                    .range((day, PARSE_STEP)..=(day, 2))
+                    .map(|(_, nanos)| *nanos)
+                    .collect();
+
+                (!nanos.is_empty()).then(|| Duration::from_nanos(nanos.iter().sum()))
+            })
+    })
+}
+
+fn to_json(state: &DayState) -> String {
+    let tests = state
+        .tests
+        .map(|passed| if passed { "passed" } else { "failed" });
+    let run = state.run.as_ref().map(ToString::to_string);
+
+    let writer = ObjectWriter::new()
+        .number("day", state.day.into_inner())
+        .bool("bin", state.has_bin)
+        .bool("input", state.has_input)
+        .bool("example", state.has_example)
+        .opt_string("tests", tests)
+        .opt_string("run", run.as_deref());
+
+    [1, 2]
+        .into_iter()
+        .zip(&state.parts)
+        .fold(writer, |writer, (part, state)| {
+            writer
+                .opt_string(
+                    &format!("part_{part}"),
+                    state.status.as_ref().map(Status::as_str),
+                )
+                .bool(&format!("part_{part}_recorded"), state.recorded)
+                .opt_string(
+                    &format!("part_{part}_submitted"),
+                    state.verdict.map(Verdict::as_str),
+                )
+        })
+        .opt_number("time_nanos", state.timing.map(|timing| timing.as_nanos()))
+        .finish()
+}
+
+/// Pads `text` to `width` before coloring it, as the escape codes would count towards the width.
+fn cell(text: &str, width: usize, color: &str) -> String {
+    format!("{color}{text:<width$}{ANSI_RESET}")
+}
+
+/// `✔` if a file exists, `-` if it does not yet.
+fn exists_cell(value: bool, width: usize) -> String {
+    if value {
+        cell("✔", width, ANSI_GREEN)
+    } else {
+        cell("-", width, "")
+    }
+}
+
+fn check_cell(value: Option<bool>, width: usize) -> String {
+    match value {
+        Some(true) => cell("✔", width, ANSI_GREEN),
+        Some(false) => cell("✘", width, ANSI_RED),
+        None => cell("-", width, ""),
+    }
+}
+
+fn part_cell(state: &PartState, run: Option<&DayStatus>) -> String {
+    let (text, color) = match (&state.status, run) {
+        (Some(status), _) => {
+            let color = match status {
+                Status::Verified => ANSI_GREEN,
+                Status::Solved => ANSI_YELLOW,
+                Status::Unsolved => "",
+                Status::Mismatch(_) | Status::Error(_) => ANSI_RED,
+            };
+            (status.as_str().to_string(), color)
+        }
+        (None, Some(run)) if run.is_failure() => (run.to_string(), ANSI_RED),
+        (None, _) => ("-".into(), ""),
+    };
+
+    let mark = match state.verdict {
+        Some(Verdict::Correct) => " ★",
+        Some(verdict) if verdict.is_wrong() => " ✘",
+        _ => "",
+    };
+
+    cell(&format!("{text}{mark}"), 12, color)
+}
+
+fn print_table(year: Year, states: &[DayState]) {
+    println!("{ANSI_BOLD}Status {year}{ANSI_RESET}");
+    println!("------");
+    println!("| Day | Bin | Input | Example | Tests | Part 1       | Part 2       | Time");
+    println!("| :-: | :-: | :---: | :-----: | :---: | :----------- | :----------- | ---:");
+
+    for state in states {
+        let [part_1, part_2] = &state.parts;
+        let time = state
+            .timing
+            .map_or_else(|| "-".into(), |timing| format!("{timing:.1?}"));
+
+        println!(
+            "| {}  | {} | {} | {} | {} | {} | {} | {time}",
+            state.day,
+            exists_cell(state.has_bin, 3),
+            exists_cell(state.has_input, 5),
+            exists_cell(state.has_example, 7),
+            check_cell(state.tests, 5),
+            part_cell(part_1, state.run.as_ref()),
+            part_cell(part_2, state.run.as_ref()),
+        );
+    }
+
+    let count = |f: fn(&PartState) -> bool| {
+        states
+            .iter()
+            .flat_map(|state| &state.parts)
+            .filter(|part| f(part))
+            .count()
+    };
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {}, {ANSI_BOLD}Submitted:{ANSI_RESET} {} of {} parts. ★ marks a correct submission, ✘ a wrong one.",
+        count(|part| part.status == Some(Status::Verified)),
+        count(|part| part.verdict == Some(Verdict::Correct)),
+        states.len() * 2,
+    );
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find_timing, get_verdict, parse_test_results};
+    use crate::template::benchmark_history::Run;
+    use crate::template::submissions::{History, Submission, Verdict};
+    use crate::{day, year};
+    use std::time::Duration;
+
+    #[test]
+    fn finds_timings() {
+        let run = |release: bool, timings: &[(u8, u8, u64)]| Run {
+            timestamp: 1000,
+            commit: None,
+            release,
+            timings: timings
+                .iter()
+                .map(|&(day, part, nanos)| ((crate::Day::new(day).unwrap(), part), nanos))
+                .collect(),
+        };
+
+        let runs = vec![
+            run(true, &[(1, 0, 10), (1, 1, 100), (1, 2, 1000), (2, 1, 5)]),
+            run(true, &[(2, 1, 7)]),
+            run(false, &[(1, 1, 1), (3, 1, 50)]),
+        ];
+
+        assert_eq!(
+            find_timing(&runs, day!(1)),
+            Some(Duration::from_nanos(1110))
+        );
+        assert_eq!(find_timing(&runs, day!(2)), Some(Duration::from_nanos(7)));
+        assert_eq!(find_timing(&runs, day!(3)), Some(Duration::from_nanos(50)));
+        assert_eq!(find_timing(&runs, day!(4)), None);
+    }
+
+    #[test]
+    fn parses_test_results() {
+        let output = "\
+   Compiling advent_of_code v0.9.2 (/root/crate)
+     Running unittests src/bin/2023-01.rs (target/debug/deps/2023_01-1f2e)
+
+running 2 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 2 filtered out
+
+     Running unittests src/bin/2022-02.rs (target/debug/deps/2022_02-3a4b)
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+
+     Running unittests src/bin/2023-02.rs (target/debug/deps/2023_02-5c6d)
+
+running 1 test
+test tests::test_part_one ... FAILED
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+
+     Running unittests src/main.rs (target/debug/deps/advent_of_code-7e8f)
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+        let results = parse_test_results(year!(2023), output);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.get(&day!(1)), Some(&true));
+        assert_eq!(results.get(&day!(2)), Some(&false));
+    }
+
+    #[test]
+    fn gets_verdicts() {
+        let submission = |part: u8, verdict: Verdict| Submission {
+            timestamp: 1000,
+            part,
+            answer: "42".into(),
+            verdict,
+            cooldown_until: None,
+        };
+
+        let history = History {
+            submissions: vec![
+                submission(1, Verdict::TooLow),
+                submission(1, Verdict::Correct),
+                submission(1, Verdict::AlreadyCompleted),
+                submission(2, Verdict::TooLow),
+                submission(2, Verdict::TooHigh),
+            ],
+        };
+
+        assert_eq!(get_verdict(&history, 1), Some(Verdict::Correct));
+        assert_eq!(get_verdict(&history, 2), Some(Verdict::TooHigh));
+        assert_eq!(get_verdict(&History::default(), 1), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    io::{stdout, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    allocator::get_feature_args, count_examples, get_example_file_name, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET,
+};
+use crate::{Day, Year};
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn get_watched_paths(year: Year, day: Day) -> Vec<String> {
+    let mut paths = vec![
+        format!("src/bin/{year}-{day}.rs"),
+        format!("data/{year}/examples/{day}.txt"),
+    ];
+
+    // per-part examples that don't exist yet are watched as well, so creating one triggers a run.
+    for part in 1..=2 {
+        let count = count_examples(year, day, part);
+        for index in 0..=count {
+            paths.push(format!(
+                "data/{year}/examples/{}",
+                get_example_file_name(day, part, index)
+            ));
+        }
+    }
+
+    paths.push(format!("data/{year}/inputs/{day}.txt"));
+    paths
+}
+
+/// Modification times of the watched files. Missing files are recorded as [`None`].
+fn get_mtimes(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+pub fn handle(year: Year, day: Day, release: bool) {
+    let paths = get_watched_paths(year, day);
+    let mut last_mtimes = get_mtimes(&paths);
+
+    run(year, day, release, &paths);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let mtimes = get_mtimes(&paths);
+
+        if mtimes != last_mtimes {
+            // editors often write files in several steps, give them a moment to finish.
+            thread::sleep(POLL_INTERVAL);
+            last_mtimes = get_mtimes(&paths);
+            run(year, day, release, &paths);
+        }
+    }
+}
+
+fn run(year: Year, day: Day, release: bool, paths: &[String]) {
+    print!("{ANSI_CLEAR_SCREEN}");
+    println!(
+        "{ANSI_BOLD}{year} Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching {}){ANSI_RESET}",
+        paths.join(", ")
+    );
+    println!("------");
+    print!("Running example tests...");
+    let _ = stdout().flush();
+
+    let bin = format!("{year}-{day}");
+    let mut test_args = vec!["test", "--quiet", "--bin", &bin];
+
+    if release {
+        test_args.push("--release");
+    }
+
+    // the real input tests would re-run the whole input on every save.
+    test_args.extend(["--", "--skip", "real_input"]);
+
+    match Command::new("cargo")
+        .args(&test_args)
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) if output.status.success() => println!("\r✔ Example tests passed.  "),
+        Ok(output) => {
+            println!("\r✘ Example tests failed.  ");
+            println!();
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        Err(e) => {
+            println!("\r✘ Could not run example tests: {e}");
+        }
+    }
+
+    println!();
+
+    let mut run_args = vec!["run", "--quiet", "--bin", &bin];
+
+    if release {
+        run_args.push("--release");
+    }
+
+    run_args.extend(get_feature_args());
+
+    let status = Command::new("cargo")
+        .args(&run_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("\n✘ Solution exited with {status}."),
+        Err(e) => println!("\n✘ Could not run solution: {e}"),
+    }
+
+    println!("\n{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+}
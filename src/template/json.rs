@@ -0,0 +1,277 @@
+/// Minimal JSON support for the line-based protocol between day binaries and the `all` command.
+/// Only flat objects with string, number, boolean and null values are supported.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Numbers are kept in their textual form so that integers of any size survive a round-trip.
+    Number(String),
+    String(String),
+}
+
+impl Value {
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A builder for a single-line JSON object.
+#[derive(Default)]
+pub struct ObjectWriter {
+    buf: String,
+}
+
+impl ObjectWriter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(&mut self, key: &str) {
+        self.buf.push(if self.buf.is_empty() { '{' } else { ',' });
+        self.buf.push_str(&escape(key));
+        self.buf.push(':');
+    }
+
+    #[must_use]
+    pub fn string(mut self, key: &str, value: &str) -> Self {
+        self.key(key);
+        self.buf.push_str(&escape(value));
+        self
+    }
+
+    #[must_use]
+    pub fn opt_string(mut self, key: &str, value: Option<&str>) -> Self {
+        match value {
+            Some(value) => self.string(key, value),
+            None => {
+                self.key(key);
+                self.buf.push_str("null");
+                self
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn number(mut self, key: &str, value: impl Into<u128>) -> Self {
+        self.key(key);
+        let _ = write!(self.buf, "{}", value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn opt_number(mut self, key: &str, value: Option<impl Into<u128>>) -> Self {
+        match value {
+            Some(value) => self.number(key, value),
+            None => {
+                self.key(key);
+                self.buf.push_str("null");
+                self
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn bool(mut self, key: &str, value: bool) -> Self {
+        self.key(key);
+        let _ = write!(self.buf, "{value}");
+        self
+    }
+
+    #[must_use]
+    pub fn finish(mut self) -> String {
+        if self.buf.is_empty() {
+            self.buf.push('{');
+        }
+        self.buf.push('}');
+        self.buf
+    }
+}
+
+/// Encodes a string as a quoted JSON string.
+#[must_use]
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// Parses a flat JSON object. Returns [`None`] if the input is not one.
+#[must_use]
+pub fn parse_object(s: &str) -> Option<HashMap<String, Value>> {
+    let mut parser = Parser {
+        chars: s.trim().chars().peekable(),
+    };
+    let mut object = HashMap::new();
+
+    parser.expect('{')?;
+
+    if parser.peek_non_ws() == Some('}') {
+        parser.chars.next();
+    } else {
+        loop {
+            let key = parser.string()?;
+            parser.expect(':')?;
+            let value = parser.value()?;
+            object.insert(key, value);
+
+            match parser.next_non_ws()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+
+    parser.chars.next().is_none().then_some(object)
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn peek_non_ws(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn next_non_ws(&mut self) -> Option<char> {
+        self.peek_non_ws()?;
+        self.chars.next()
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        (self.next_non_ws()? == expected).then_some(())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let mut out = String::new();
+
+        loop {
+            match self.chars.next()? {
+                '"' => return Some(out),
+                '\\' => match self.chars.next()? {
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'u' => {
+                        let code: String =
+                            (0..4).map(|_| self.chars.next()).collect::<Option<_>>()?;
+                        out.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                    }
+                    c @ ('"' | '\\' | '/') => out.push(c),
+                    _ => return None,
+                },
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn literal(&mut self) -> String {
+        let mut out = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+        {
+            out.push(c);
+        }
+        out
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        match self.peek_non_ws()? {
+            '"' => self.string().map(Value::String),
+            _ => match self.literal().as_str() {
+                "null" => Some(Value::Null),
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                n if n.parse::<f64>().is_ok() => Some(Value::Number(n.to_string())),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{escape, parse_object, ObjectWriter, Value};
+
+    #[test]
+    fn round_trips_objects() {
+        let line = ObjectWriter::new()
+            .number("part", 1_u8)
+            .string("answer", "a \"quoted\"\nanswer\\")
+            .opt_string("expected", None)
+            .number("duration_nanos", u128::MAX)
+            .opt_number("time_nanos", None::<u64>)
+            .finish();
+
+        let object = parse_object(&line).unwrap();
+        assert_eq!(object["part"].as_u128(), Some(1));
+        assert_eq!(object["answer"].as_str(), Some("a \"quoted\"\nanswer\\"));
+        assert_eq!(object["expected"], Value::Null);
+        assert_eq!(object["duration_nanos"].as_u128(), Some(u128::MAX));
+        assert_eq!(object["time_nanos"], Value::Null);
+    }
+
+    #[test]
+    fn parses_whitespace_and_escapes() {
+        let object = parse_object(r#" { "a" : true , "b": "\u00e9\t", "c": -1.5 } "#).unwrap();
+        assert_eq!(object["a"], Value::Bool(true));
+        assert_eq!(object["b"].as_str(), Some("é\t"));
+        assert_eq!(object["c"], Value::Number("-1.5".into()));
+        assert_eq!(parse_object("{}").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(parse_object("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(parse_object(r#"{"a": 1"#), None);
+        assert_eq!(parse_object(r#"{"a": 1} trailing"#), None);
+        assert_eq!(parse_object(r#"{"a": nope}"#), None);
+        assert_eq!(escape("\u{1}"), "\"\\u0001\"");
+    }
+}
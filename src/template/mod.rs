@@ -1,36 +1,383 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Year};
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
 
-pub mod aoc_cli;
+pub mod allocator;
+pub mod answers;
+pub mod aoc_client;
+pub mod benchmark_export;
+pub mod benchmark_history;
 pub mod commands;
+pub mod json;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string, see [`try_read_file`].
+///
+/// # Panics
+///
+/// If the file is missing or empty.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    try_read_file(folder, year, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads `data/YYYY/<folder>/NN.txt` and prepares it with [`prepare_input`].
+/// A missing or empty file is an error that says how to fill it.
+pub fn try_read_file(folder: &str, year: Year, day: Day) -> Result<String, String> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let filepath = cwd
+        .join("data")
+        .join(year.to_string())
+        .join(folder)
+        .join(format!("{day}.txt"));
+    let path = format!("data/{year}/{folder}/{day}.txt");
+
+    let hint = match folder {
+        "examples" => format!(
+            "Run `cargo download {day} --year {year} --example` or paste the example into it."
+        ),
+        _ => format!("Run `cargo download {day} --year {year}` to download it."),
+    };
+
+    match fs::read_to_string(filepath) {
+        Ok(content) if content.trim().is_empty() => Err(format!("\"{path}\" is empty. {hint}")),
+        Ok(content) => Ok(prepare_input(&content, &path)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Err(format!("\"{path}\" does not exist. {hint}"))
+        }
+        Err(e) => Err(format!("Could not read \"{path}\": {e}")),
+    }
+}
+
+/// Whether the input of a day has content. A day without one is skipped rather than failed.
+#[must_use]
+pub fn has_input(year: Year, day: Day) -> bool {
+    fs::read_to_string(format!("data/{year}/inputs/{day}.txt"))
+        .is_ok_and(|content| !content.trim().is_empty())
+}
+
+/// Normalises CRLF line endings, so that solutions only have to split on `\n`,
+/// and warns about trailing whitespace that a downloaded input does not have.
+#[must_use]
+pub fn prepare_input(content: &str, name: &str) -> String {
+    let content = content.replace("\r\n", "\n");
+
+    if let Some(warning) = check_trailing_whitespace(&content) {
+        eprintln!("Warning: \"{name}\" {warning}");
+    }
+
+    content
+}
+
+/// Describes trailing whitespace that puzzle inputs never have, as it is likely to break a solution
+/// that works on the real input.
+fn check_trailing_whitespace(content: &str) -> Option<String> {
+    let lines = content
+        .lines()
+        .filter(|line| line.ends_with([' ', '\t']))
+        .count();
+
+    if lines > 0 {
+        Some(format!("has {lines} line(s) that end in whitespace."))
+    } else if content.ends_with("\n\n") {
+        Some("ends with blank lines.".into())
+    } else {
+        None
+    }
+}
+
+/// Suffix that tells the examples of a part apart: none for the first, `b`, `c`, ... for the ones after.
+#[must_use]
+pub fn get_example_suffix(index: usize) -> String {
+    match index {
+        0 => String::new(),
+        _ => (b'a'..=b'z')
+            .map(char::from)
+            .nth(index)
+            .map(String::from)
+            .unwrap_or_default(),
+    }
+}
+
+/// Name of the `index`-th example file of a part, e.g. `08-2.txt` for the first and `08-2b.txt` for the second.
+#[must_use]
+pub fn get_example_file_name(day: Day, part: u8, index: usize) -> String {
+    format!("{day}-{part}{}.txt", get_example_suffix(index))
+}
+
+fn get_examples_dir(year: Year) -> PathBuf {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    cwd.join("data").join(year.to_string()).join("examples")
+}
+
+/// Reads the `index`-th example of a part from `data/YYYY/examples/NN-P.txt`, `NN-Pb.txt`, ...
+/// The first example of a part falls back to the shared `NN.txt` if the part has no example file of its own.
+#[must_use]
+pub fn read_example(year: Year, day: Day, part: u8, index: usize) -> String {
+    let filepath = get_examples_dir(year).join(get_example_file_name(day, part, index));
+
+    if index == 0 && !filepath.exists() {
+        return read_file("examples", year, day);
+    }
+
+    let f = fs::read_to_string(&filepath).expect("could not open example file");
+    prepare_input(&f, &filepath.to_string_lossy())
+}
+
+/// Counts the consecutive example files of a part, i.e. `NN-P.txt`, `NN-Pb.txt`, ...
+#[must_use]
+pub fn count_examples(year: Year, day: Day, part: u8) -> usize {
+    let dir = get_examples_dir(year);
+    (0..26)
+        .take_while(|&index| dir.join(get_example_file_name(day, part, index)).exists())
+        .count()
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Checks the answer of a part for the real input against the recorded answer, see [`answers`].
+/// Used by the tests that `solution!` generates. Without an input or a recorded answer there is nothing
+/// to check against, e.g. on CI, so the check is skipped with a message.
+#[track_caller]
+pub fn check_real_input(
+    year: Year,
+    day: Day,
+    part: u8,
+    solve: impl FnOnce(&str) -> Result<Option<String>, String>,
+) {
+    let skip = |reason: &str| {
+        // written to stderr directly, as the test harness would swallow `eprintln!`.
+        let _ = writeln!(
+            io::stderr(),
+            "skipping real input test of day {day} part {part}: {reason}."
+        );
+    };
+
+    let input = match try_read_file("inputs", year, day) {
+        Ok(input) => input,
+        Err(e) => return skip(&e),
+    };
+
+    let expected = match answers::read(year, day) {
+        Ok(answers) => answers.get(part).map(String::from),
+        Err(e) => panic!("could not read the recorded answers: {e:?}"),
+    };
+
+    let Some(expected) = expected else {
+        return skip("no answer is recorded");
+    };
+
+    let answer =
+        solve(&input).unwrap_or_else(|e| panic!("day {day} part {part} returned an error: {e}"));
+
+    assert_eq!(
+        answer.as_deref(),
+        Some(expected.as_str()),
+        "day {day} part {part} differs from the recorded answer"
+    );
+}
+
+/// Parses the number of a puzzle part, which is either `1` or `2`.
+pub fn parse_part(s: &str) -> Result<u8, &'static str> {
+    match s.trim() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err("expecting part 1 or 2"),
+    }
+}
+
+/// Parses the size of a thread pool, which must be at least one.
+pub fn parse_threads(s: &str) -> Result<usize, &'static str> {
+    match s.trim().parse() {
+        Ok(0) | Err(_) => Err("expecting a positive number of threads"),
+        Ok(threads) => Ok(threads),
+    }
+}
+
+/// Parses a human-readable duration such as `250ms`, `2s` or `1.5m`.
+/// Supported units are `ns`, `µs` (or `us`), `ms`, `s` and `m`.
+pub fn parse_duration(s: &str) -> Result<Duration, &'static str> {
+    let s = s.trim();
+    let split_at = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or("missing duration unit")?;
+    let (value, unit) = s.split_at(split_at);
+    let value: f64 = value.parse().map_err(|_| "invalid duration value")?;
+
+    let nanos_per_unit = match unit {
+        "ns" => 1_f64,
+        "µs" | "us" => 1_000_f64,
+        "ms" => 1_000_000_f64,
+        "s" => 1_000_000_000_f64,
+        "m" => 60_000_000_000_f64,
+        _ => return Err("unknown duration unit"),
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(Duration::from_nanos((value * nanos_per_unit).round() as u64))
+}
+
+/// Creates the constants `YEAR` and `DAY`, sets up the input and runner for each part
+/// and registers the solution in the [`registry`].
+///
+/// With `solution!(2023, 5, parse = parse_almanac)`, the input is parsed once by `parse_almanac`
+/// and both parts take a reference to its output. The parse step is timed separately from the parts.
+/// Its output can not borrow from the input.
+///
+/// Every part also gets a test that checks it against the answer recorded for the real input,
+/// see [`check_real_input`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+    ($year:expr, $day:expr) => {
+        advent_of_code::solution!(@constants $year, $day);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            configure_threads();
+            let input = read_input(YEAR, DAY);
+            run_part(part_one, &input, YEAR, DAY, 1);
+            run_part(part_two, &input, YEAR, DAY, 2);
+        }
+
+        advent_of_code::solution!(@register |input: &str| input.to_string(), false);
+        advent_of_code::solution!(@real_input_tests |input: &str| input.to_string());
+    };
+    ($year:expr, $day:expr, parse = $parse:path) => {
+        advent_of_code::solution!(@constants $year, $day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            configure_threads();
+            let input = read_input(YEAR, DAY);
+            let parsed = run_parse($parse, &input, DAY);
+            run_part(part_one, &parsed, YEAR, DAY, 1);
+            run_part(part_two, &parsed, YEAR, DAY, 2);
+        }
+
+        advent_of_code::solution!(@register $parse, true);
+        advent_of_code::solution!(@real_input_tests $parse);
+    };
+    (@constants $year:expr, $day:expr) => {
+        /// The year of the event.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+    };
+    (@real_input_tests $parse:expr) => {
+        #[cfg(test)]
+        mod real_input {
+            // the tests shadow the parts, which are therefore called through `super`.
+            use super::*;
+            use advent_of_code::template::runner::PartOutput;
+
+            #[test]
+            fn part_one() {
+                advent_of_code::template::check_real_input(super::YEAR, super::DAY, 1, |input| {
+                    let answer = super::part_one(&($parse)(input)).into_answer()?;
+                    Ok(answer.map(|answer| answer.to_string()))
+                });
+            }
+
+            #[test]
+            fn part_two() {
+                advent_of_code::template::check_real_input(super::YEAR, super::DAY, 2, |input| {
+                    let answer = super::part_two(&($parse)(input)).into_answer()?;
+                    Ok(answer.map(|answer| answer.to_string()))
+                });
+            }
         }
     };
+    (@register $parse:expr, $has_parse_step:expr) => {
+        /// The solution of the day, registered so it can be run in-process.
+        struct __Solution;
+
+        impl advent_of_code::template::registry::Solution for __Solution {
+            fn metadata(&self) -> advent_of_code::template::registry::Metadata {
+                advent_of_code::template::registry::Metadata {
+                    year: YEAR,
+                    day: DAY,
+                }
+            }
+
+            fn parse(&self, input: &str) -> Box<dyn std::any::Any> {
+                Box::new(($parse)(input))
+            }
+
+            fn has_parse_step(&self) -> bool {
+                $has_parse_step
+            }
+
+            fn part_one(&self, parsed: &dyn std::any::Any) -> Result<Option<String>, String> {
+                use advent_of_code::template::runner::PartOutput;
+                let parsed = advent_of_code::template::registry::downcast($parse, parsed)
+                    .ok_or("the parsed input has an unexpected type")?;
+                let answer = part_one(parsed).into_answer()?;
+                Ok(answer.map(|answer| answer.to_string()))
+            }
+
+            fn part_two(&self, parsed: &dyn std::any::Any) -> Result<Option<String>, String> {
+                use advent_of_code::template::runner::PartOutput;
+                let parsed = advent_of_code::template::registry::downcast($parse, parsed)
+                    .ok_or("the parsed input has an unexpected type")?;
+                let answer = part_two(parsed).into_answer()?;
+                Ok(answer.map(|answer| answer.to_string()))
+            }
+        }
+
+        advent_of_code::template::registry::inventory::submit! {
+            advent_of_code::template::registry::Registration(&__Solution)
+        }
+    };
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        check_real_input, check_trailing_whitespace, get_example_file_name, parse_threads,
+        prepare_input,
+    };
+    use crate::{day, year};
+
+    #[test]
+    fn parses_threads() {
+        assert_eq!(parse_threads("4"), Ok(4));
+        assert!(parse_threads("0").is_err());
+        assert!(parse_threads("all").is_err());
+    }
+
+    #[test]
+    fn names_example_files() {
+        assert_eq!(get_example_file_name(day!(8), 1, 0), "08-1.txt");
+        assert_eq!(get_example_file_name(day!(8), 2, 1), "08-2b.txt");
+        assert_eq!(get_example_file_name(day!(10), 2, 3), "10-2d.txt");
+    }
+
+    #[test]
+    fn skips_real_input_without_input() {
+        check_real_input(year!(2015), day!(1), 1, |_| {
+            panic!("the part must not run without an input")
+        });
+    }
+
+    #[test]
+    fn prepares_inputs() {
+        assert_eq!(prepare_input("a b\r\nc\r\n", "input"), "a b\nc\n");
+        assert_eq!(check_trailing_whitespace("a b\nc\n"), None);
+        assert_eq!(check_trailing_whitespace("a b\nc"), None);
+        assert!(check_trailing_whitespace("a b \nc\t\n").is_some_and(|w| w.contains("2 line(s)")));
+        assert!(check_trailing_whitespace("a b\nc\n\n").is_some());
+    }
 }
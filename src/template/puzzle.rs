@@ -0,0 +1,230 @@
+/// Module that extracts puzzle descriptions, example inputs and example answers from a puzzle page.
+use std::{fs, io, process};
+
+use crate::template::aoc_client::AocClient;
+use crate::{Day, Year};
+
+/// Loads the HTML of a puzzle page, either from a local file or from adventofcode.com.
+/// Exits the process if the page can't be loaded.
+#[must_use]
+pub fn load_html(year: Year, day: Day, html_path: Option<&str>) -> String {
+    if let Some(path) = html_path {
+        return fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read puzzle page \"{path}\": {e}");
+            process::exit(1);
+        });
+    }
+
+    let client = AocClient::from_env(year).unwrap_or_else(|e| {
+        eprintln!("Failed to set up Advent of Code client: {e}");
+        process::exit(1);
+    });
+
+    client.get_puzzle(day).unwrap_or_else(|e| {
+        eprintln!("Failed to download puzzle page: {e}");
+        process::exit(1);
+    })
+}
+
+#[must_use]
+pub fn get_path_for_puzzle(year: Year, day: Day) -> String {
+    format!("data/{year}/puzzles/{day}.md")
+}
+
+/// Writes the example input of a puzzle page to `data/YYYY/examples/NN.txt`, unless that file already has content.
+/// Returns whether the file was written.
+pub fn populate_example(year: Year, day: Day, html: &str) -> Result<bool, io::Error> {
+    let example_path = format!("data/{year}/examples/{day}.txt");
+
+    let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+    if !is_empty {
+        return Ok(false);
+    }
+
+    match extract_example(html) {
+        Some(example) => {
+            fs::create_dir_all(format!("data/{year}/examples"))?;
+            fs::write(&example_path, example).map(|()| true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Returns the inner HTML of every `<article>` of the page, i.e. one per unlocked part.
+fn get_articles(html: &str) -> Vec<&str> {
+    html.split("<article")
+        .skip(1)
+        .filter_map(|rest| {
+            let (_, rest) = rest.split_once('>')?;
+            let (article, _) = rest.split_once("</article>")?;
+            Some(article)
+        })
+        .collect()
+}
+
+/// Returns the text of the first `<pre><code>` block, which is usually the example input.
+#[must_use]
+pub fn extract_example(html: &str) -> Option<String> {
+    let article = get_articles(html).into_iter().next()?;
+    let (_, rest) = article.split_once("<pre><code>")?;
+    let (code, _) = rest.split_once("</code></pre>")?;
+    Some(decode_entities(&strip_tags(code)))
+}
+
+/// Returns the expected example answer of each unlocked part.
+/// Puzzles highlight the example answer as the last `<code><em>…</em></code>` of the part's description.
+#[must_use]
+pub fn extract_example_answers(html: &str) -> [Option<String>; 2] {
+    let articles = get_articles(html);
+
+    let answer = |index: usize| {
+        let article = articles.get(index)?;
+        let (_, rest) = article.rsplit_once("<code><em>")?;
+        let (answer, _) = rest.split_once("</em></code>")?;
+        Some(decode_entities(&strip_tags(answer)))
+    };
+
+    [answer(0), answer(1)]
+}
+
+/// Converts the puzzle description(s) of a page to markdown.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+
+    for article in get_articles(html) {
+        // emphasized code renders as **`code`** rather than `**code**`.
+        let article = article
+            .replace("<code><em>", "<em><code>")
+            .replace("</em></code>", "</code></em>");
+        let mut rest = article.as_str();
+
+        while let Some(start) = rest.find('<') {
+            markdown.push_str(&decode_entities(&rest[..start]));
+            rest = &rest[start..];
+
+            let Some(end) = rest.find('>') else {
+                break;
+            };
+
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            // code blocks are copied verbatim.
+            if tag == "pre" {
+                let (block, after) = rest.split_once("</pre>").unwrap_or((rest, ""));
+                let code = decode_entities(&strip_tags(block));
+                markdown.push_str("```\n");
+                markdown.push_str(code.trim_end_matches('\n'));
+                markdown.push_str("\n```\n\n");
+                rest = after;
+                continue;
+            }
+
+            let name = tag.split_whitespace().next().unwrap_or_default();
+
+            match name {
+                "h2" => markdown.push_str("## "),
+                "/h2" | "/p" | "/ul" => markdown.push_str("\n\n"),
+                "li" => markdown.push_str("- "),
+                "/li" => markdown.push('\n'),
+                "code" | "/code" => markdown.push('`'),
+                "em" | "/em" => markdown.push_str("**"),
+                "a" => markdown.push('['),
+                "/a" => markdown.push(']'),
+                _ => {}
+            }
+
+            if name == "a" {
+                if let Some(href) = tag
+                    .split_once("href=\"")
+                    .and_then(|(_, href)| href.split_once('"'))
+                    .map(|(href, _)| href)
+                {
+                    // the link target is appended once the link text is complete.
+                    let (text, after) = rest.split_once("</a>").unwrap_or((rest, ""));
+                    markdown.push_str(&decode_entities(&strip_tags(text)));
+                    markdown.push_str(&format!("]({href})"));
+                    rest = after;
+                }
+            }
+        }
+
+        markdown.push_str(&decode_entities(rest));
+    }
+
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+
+    format!("{}\n", markdown.trim())
+}
+
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+pub(crate) fn decode_entities(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{extract_example, extract_example_answers, to_markdown};
+
+    const PAGE: &str = concat!(
+        "<html><body><main>",
+        "<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>",
+        "<p>For <em>example</em>, see <a href=\"/2023/about\">here</a>:</p>",
+        "<pre><code>1abc2\npqr3stu8vwx\na&lt;b\n</code></pre>",
+        "<ul><li>one</li><li>two</li></ul>",
+        "<p>Adding these together produces <code><em>142</em></code>.</p></article>",
+        "<p>Your puzzle answer was <code>55208</code>.</p>",
+        "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>",
+        "<pre><code>two1nine\n</code></pre>",
+        "<p>Adding these together produces <code><em>281</em></code>.</p></article>",
+        "</main></body></html>"
+    );
+
+    #[test]
+    fn extracts_example() {
+        assert_eq!(
+            extract_example(PAGE).as_deref(),
+            Some("1abc2\npqr3stu8vwx\na<b\n")
+        );
+        assert_eq!(extract_example("<html></html>"), None);
+    }
+
+    #[test]
+    fn extracts_example_answers() {
+        assert_eq!(
+            extract_example_answers(PAGE),
+            [Some("142".into()), Some("281".into())]
+        );
+    }
+
+    #[test]
+    fn converts_to_markdown() {
+        let markdown = to_markdown(PAGE);
+        assert!(markdown.starts_with("## --- Day 1: Trebuchet?! ---\n\nFor **example**, see [here](/2023/about):\n\n```\n1abc2\npqr3stu8vwx\na<b\n```\n\n- one\n- two\n\n"));
+        assert!(markdown.contains("produces **`142`**."));
+        assert!(markdown.contains("## --- Part Two ---"));
+        assert!(!markdown.contains("55208"));
+    }
+}
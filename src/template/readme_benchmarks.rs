@@ -1,10 +1,32 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// A table lives between two identical markers, which can name the runs the table shows and configure it.
+/// A single marker is replaced by a table between two of them. Markers in inline code are ignored.
+///
+/// ```md
+/// <!--- benchmarking table 2023-debug columns=status,samples sort=cost highlight=3 subtotal --->
+/// <!--- benchmarking table 2023-debug columns=status,samples sort=cost highlight=3 subtotal --->
+/// ```
+///
+/// The name is a year, a profile (`release` or `debug`) or both, e.g. `2023`, `debug` or `2023-debug`.
+/// Markers without a year show every year, markers without a profile show release runs.
+/// If a table names the year of a run, the tables without a year are left alone.
+///
+/// - `columns` lists what is shown for every part next to its median:
+///   `status`, `stddev`, `p95`, `samples` and `allocations`. Defaults to all but `status`.
+/// - `sort=cost` puts the slowest days first, `sort=day` (the default) keeps them in order.
+/// - `highlight=N` puts the `N` slowest days in bold.
+/// - `subtotal` adds a row with the sum of every part.
+use std::{fs, io, time::Duration};
 
-use crate::Day;
+use crate::template::allocator::format_bytes;
+use crate::template::runner::{BenchStats, Status, PARSE_STEP};
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_START: &str = "<!--- benchmarking table";
+static MARKER_END: &str = "--->";
 
 #[derive(Debug)]
 pub enum Error {
@@ -21,110 +43,492 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Timing of the parse step, if the solution has one.
+    pub parse: Option<BenchStats>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
+    /// Whether the answer of part 1 was verified, if the part ran.
+    pub status_1: Option<Status>,
+    /// Whether the answer of part 2 was verified, if the part ran.
+    pub status_2: Option<Status>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// The timing and status of a part, or of the parse step for [`PARSE_STEP`].
+    #[must_use]
+    pub fn get(&self, step: u8) -> (Option<&BenchStats>, Option<&Status>) {
+        match step {
+            PARSE_STEP => (self.parse.as_ref(), None),
+            1 => (self.part_1.as_ref(), self.status_1.as_ref()),
+            _ => (self.part_2.as_ref(), self.status_2.as_ref()),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
+/// Information that can be shown for every part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Status,
+    Stddev,
+    P95,
+    Samples,
+    Allocations,
+}
+
+impl Column {
+    fn parse(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "status" => Column::Status,
+            "stddev" => Column::Stddev,
+            "p95" => Column::P95,
+            "samples" => Column::Samples,
+            "allocations" => Column::Allocations,
+            _ => return Err(Error::Parser(format!("unknown column `{s}`."))),
+        })
+    }
+}
+
+/// The configuration of a table, read from its marker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableConfig {
+    year: Option<Year>,
+    /// Whether the table shows release or debug runs.
+    release: bool,
+    columns: Vec<Column>,
+    sort_by_cost: bool,
+    highlight: usize,
+    subtotal: bool,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            year: None,
+            release: true,
+            columns: vec![
+                Column::Stddev,
+                Column::P95,
+                Column::Samples,
+                Column::Allocations,
+            ],
+            sort_by_cost: false,
+            highlight: 0,
+            subtotal: false,
+        }
+    }
+}
+
+impl TableConfig {
+    fn matches(&self, year: Year, release: bool) -> bool {
+        self.year.is_none_or(|y| y == year) && self.release == release
+    }
+
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+fn parse_marker(marker: &str) -> Result<TableConfig, Error> {
+    let options = marker
+        .trim_start_matches(MARKER_START)
+        .trim_end_matches(MARKER_END);
+    let invalid = |what: &str| Error::Parser(format!("{marker}: {what}."));
+
+    let mut config = TableConfig::default();
+
+    for (i, token) in options.split_whitespace().enumerate() {
+        match token.split_once('=') {
+            Some(("columns", columns)) => {
+                config.columns = columns
+                    .split(',')
+                    .map(Column::parse)
+                    .collect::<Result<_, _>>()?;
+            }
+            Some(("sort", "cost")) => config.sort_by_cost = true,
+            Some(("sort", "day")) => config.sort_by_cost = false,
+            Some(("highlight", count)) => {
+                config.highlight = count
+                    .parse()
+                    .map_err(|_| invalid("invalid number of highlighted days"))?;
+            }
+            None if token == "subtotal" => config.subtotal = true,
+            None if i == 0 => {
+                for name in token.split('-') {
+                    match name {
+                        "release" => config.release = true,
+                        "debug" => config.release = false,
+                        year => {
+                            config.year =
+                                Some(year.parse().map_err(|_| invalid("invalid table name"))?);
+                        }
+                    }
+                }
+            }
+            _ => return Err(invalid(&format!("unknown option `{token}`"))),
+        }
+    }
+
+    Ok(config)
+}
+
+/// Positions of a marker in the README. A marker in inline code, e.g. where the README documents
+/// the markers, does not delimit a table.
+fn find_positions(readme: &str, marker: &str) -> Vec<usize> {
+    readme
+        .match_indices(marker)
+        .map(|(start, _)| start)
+        .filter(|&start| !readme[..start].ends_with('`'))
+        .collect()
+}
+
+/// All distinct markers in the README, in order of appearance.
+/// A pair of markers delimits a table, a single marker is replaced by a table.
+fn find_markers(readme: &str) -> Vec<&str> {
+    let mut markers: Vec<&str> = vec![];
+
+    for start in find_positions(readme, MARKER_START) {
+        let Some(len) = readme[start..].find(MARKER_END) else {
+            continue;
+        };
+
+        let marker = &readme[start..start + len + MARKER_END.len()];
+        if !markers.contains(&marker) {
+            markers.push(marker);
+        }
+    }
+
+    markers
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{year}-{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches = find_positions(readme, marker);
 
     if matches.len() > 2 {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "{}: too many occurrences of marker in README.".into(),
         ));
     }
 
     let pos_start = matches
         .first()
-        .map(|m| m.0)
+        .copied()
         .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
 
     let pos_end = matches
         .last()
-        .map(|m| m.0 + m.1.len())
+        .map(|start| start + marker.len())
         .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    config: &TableConfig,
+    year: Year,
+    timings: &[Timings],
+    total_millis: f64,
+    only_part: Option<u8>,
+) -> String {
+    let header = if config.release {
+        format!("{prefix} Benchmarks ({year})")
+    } else {
+        format!("{prefix} Benchmarks ({year}, debug)")
+    };
+
+    // the parse step gets a column of its own if any day has one.
+    let steps: Vec<u8> = if timings.iter().any(|timing| timing.parse.is_some()) {
+        vec![PARSE_STEP, 1, 2]
+    } else {
+        vec![1, 2]
+    };
+
+    let titles: Vec<String> = steps
+        .iter()
+        .map(|&step| match step {
+            PARSE_STEP => "Parse".into(),
+            part => format!("Part {part}"),
+        })
+        .collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", titles.join(" | ")),
+        format!("| :---: |{} :---:  |", " :---: |".repeat(steps.len() - 1)),
     ];
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    // the parse step runs for either part.
+    let is_skipped = |step: u8| step != PARSE_STEP && only_part.is_some_and(|only| only != step);
+
+    let mut by_cost = timings.to_vec();
+    by_cost.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+
+    let slowest: Vec<Day> = by_cost
+        .iter()
+        .take(config.highlight)
+        .map(|timing| timing.day)
+        .collect();
+
+    let rows = if config.sort_by_cost {
+        &by_cost
+    } else {
+        timings
+    };
+
+    for timing in rows {
+        let path = get_path_for_bin(year, timing.day);
+        let mut day = format!("[Day {}]({})", timing.day.into_inner(), path);
+
+        if slowest.contains(&timing.day) {
+            day = format!("**{day}**");
+        }
+
+        let cells: Vec<String> = steps
+            .iter()
+            .map(|&step| {
+                let (stats, status) = timing.get(step);
+                format_cell(config, stats, status, is_skipped(step))
+            })
+            .collect();
+
+        lines.push(format!("| {day} | {} |", cells.join(" | ")));
+    }
+
+    if config.subtotal {
+        let cells: Vec<String> = steps
+            .iter()
+            .map(|&step| {
+                if is_skipped(step) {
+                    return "_skipped_".into();
+                }
+
+                let sum: Duration = timings
+                    .iter()
+                    .filter_map(|timing| timing.get(step).0)
+                    .map(|stats| stats.median)
+                    .sum();
+                format!("`{sum:.1?}`")
+            })
+            .collect();
+
+        lines.push(format!("| **Subtotal** | {} |", cells.join(" | ")));
     }
 
+    lines.push(String::new());
+    lines.push(describe_table(config));
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// The legend below the table.
+fn describe_table(config: &TableConfig) -> String {
+    let median = if config.has(Column::Stddev) {
+        "Median ± standard deviation"
+    } else {
+        "Median"
+    };
+
+    let notes: Vec<&str> = config
+        .columns
+        .iter()
+        .filter_map(|column| match column {
+            Column::P95 => Some("p95"),
+            Column::Samples => Some("number of samples"),
+            _ => None,
+        })
+        .collect();
+
+    let mut legend = match notes.split_last() {
+        None => format!("{median} per part."),
+        Some((last, [])) => format!("{median}, {last} per part."),
+        Some((last, rest)) => format!("{median}, {} and {last} per part.", rest.join(", ")),
+    };
+
+    if config.has(Column::Status) {
+        legend.push_str(" ✔ marks verified answers, ✘ changed ones.");
+    }
+
+    match config.highlight {
+        0 => {}
+        1 => legend.push_str(" The slowest day is bold."),
+        n => legend.push_str(&format!(" The {n} slowest days are bold.")),
+    }
+
+    legend
+}
+
+fn format_cell(
+    config: &TableConfig,
+    stats: Option<&BenchStats>,
+    status: Option<&Status>,
+    is_skipped: bool,
+) -> String {
+    if is_skipped {
+        return "_skipped_".into();
+    }
+
+    let Some(stats) = stats else {
+        return "`-`".into();
+    };
+
+    let symbol = match status {
+        _ if !config.has(Column::Status) => "",
+        Some(Status::Verified) => "✔ ",
+        Some(Status::Mismatch(_) | Status::Error(_)) => "✘ ",
+        _ => "",
+    };
+
+    let time = if config.has(Column::Stddev) {
+        format!("`{:.1?} ± {:.1?}`", stats.median, stats.stddev)
+    } else {
+        format!("`{:.1?}`", stats.median)
+    };
+
+    let notes: Vec<String> = config
+        .columns
+        .iter()
+        .filter_map(|column| match column {
+            Column::P95 => Some(format!("p95 {:.1?}", stats.p95)),
+            Column::Samples => Some(format!("n={}", stats.samples)),
+            Column::Allocations => stats.alloc.map(|alloc| {
+                format!(
+                    "{} allocs, {} (peak {})",
+                    alloc.allocations,
+                    format_bytes(alloc.bytes),
+                    format_bytes(alloc.peak_bytes)
+                )
+            }),
+            Column::Status | Column::Stddev => None,
+        })
+        .collect();
+
+    if notes.is_empty() {
+        format!("{symbol}{time}")
+    } else {
+        format!("{symbol}{time} <sub>{}</sub>", notes.join(", "))
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    release: bool,
+    timings: &[Timings],
+    total_millis: f64,
+    only_part: Option<u8>,
+) -> Result<(), Error> {
+    let mut tables = find_markers(s)
+        .into_iter()
+        .map(|marker| Ok((marker.to_string(), parse_marker(marker)?)))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    tables.retain(|(_, config)| config.matches(year, release));
+
+    // a table marked for the year takes precedence over the ones of every year.
+    if tables.iter().any(|(_, config)| config.year.is_some()) {
+        tables.retain(|(_, config)| config.year.is_some());
+    }
+
+    if tables.is_empty() {
+        return Err(Error::Parser(format!(
+            "Could not find a table for this run, e.g. between two `{MARKER}` markers."
+        )));
+    }
+
+    for (marker, config) in tables {
+        let positions = locate_table(s, &marker)?;
+        let table = construct_table(
+            "##",
+            &marker,
+            &config,
+            year,
+            timings,
+            total_millis,
+            only_part,
+        );
+        s.replace_range(positions.pos_start..positions.pos_end, &table);
+    }
+
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Writes the benchmark tables of a run to the README.
+/// If only one part was run, the other part is marked as skipped.
+pub fn update(
+    year: Year,
+    release: bool,
+    timings: &[Timings],
+    total_millis: f64,
+    only_part: Option<u8>,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, release, timings, total_millis, only_part)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{format_cell, parse_marker, update_content, Column, TableConfig, Timings, MARKER};
+    use crate::template::allocator::AllocStats;
+    use crate::template::runner::{BenchStats, Status};
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn mock_stats(millis: u64) -> Option<BenchStats> {
+        Some(BenchStats {
+            samples: 100,
+            stddev: Duration::from_millis(1),
+            p95: Duration::from_millis(millis + 2),
+            ..BenchStats::single(Duration::from_millis(millis))
+        })
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
+                parse: None,
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                part_1: mock_stats(10),
+                part_2: mock_stats(20),
+                status_1: None,
+                status_2: None,
                 total_nanos: 3e+10,
             },
             Timings {
+                parse: None,
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                part_1: mock_stats(30),
+                part_2: mock_stats(40),
+                status_1: None,
+                status_2: None,
                 total_nanos: 7e+10,
             },
             Timings {
+                parse: None,
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                part_1: mock_stats(40),
+                part_2: None,
+                status_1: None,
+                status_2: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -134,47 +538,88 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
+    #[test]
+    fn updates_single_marker() {
+        let mut s = format!("foo\n\n{MARKER}\n\nbar");
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("Benchmarks (2023)"));
+        assert!(s.ends_with(&format!("{MARKER}\n\nbar")));
+
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+    }
+
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn updates_table_of_year() {
+        let mut s = format!(
+            "{MARKER}{MARKER}\n<!--- benchmarking table 2022 --->\n<!--- benchmarking table 2022 --->"
+        );
+        update_content(&mut s, year!(2022), true, &get_mock_timings(), 190.0, None).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n")));
+        assert!(s.contains("## Benchmarks (2022)"));
+        assert!(s.contains("./src/bin/2022-01.rs"));
+    }
+
+    #[test]
+    fn marks_skipped_parts() {
+        let mut s = format!("{MARKER}{MARKER}");
+        let timings = vec![Timings {
+            day: day!(1),
+            parse: None,
+            part_1: None,
+            part_2: mock_stats(20),
+            status_1: None,
+            status_2: None,
+            total_nanos: 2e+10,
+        }];
+        update_content(&mut s, year!(2023), true, &timings, 20.0, Some(2)).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | _skipped_ | `20.0ms"));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## Benchmarks (2023)",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms ± 1.0ms` <sub>p95 12.0ms, n=100</sub> | `20.0ms ± 1.0ms` <sub>p95 22.0ms, n=100</sub> |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms ± 1.0ms` <sub>p95 32.0ms, n=100</sub> | `40.0ms ± 1.0ms` <sub>p95 42.0ms, n=100</sub> |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms ± 1.0ms` <sub>p95 42.0ms, n=100</sub> | `-` |",
+            "",
+            "Median ± standard deviation, p95 and number of samples per part.",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +628,128 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn shows_allocations() {
+        let stats = BenchStats {
+            alloc: Some(AllocStats {
+                allocations: 12,
+                bytes: 2048,
+                peak_bytes: 512,
+            }),
+            ..mock_stats(10).unwrap()
+        };
+        assert!(
+            format_cell(&TableConfig::default(), Some(&stats), None, false)
+                .ends_with(", 12 allocs, 2.0 KiB (peak 512 B)</sub>")
+        );
+        assert!(!format_cell(
+            &TableConfig::default(),
+            mock_stats(10).as_ref(),
+            None,
+            false
+        )
+        .contains("allocs"));
+    }
+
+    #[test]
+    fn parses_markers() {
+        assert_eq!(parse_marker(MARKER).unwrap(), TableConfig::default());
+
+        let config = parse_marker(
+            "<!--- benchmarking table 2023-debug columns=status,samples sort=cost highlight=2 subtotal --->",
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(year!(2023)));
+        assert!(!config.release);
+        assert_eq!(config.columns, vec![Column::Status, Column::Samples]);
+        assert!(config.sort_by_cost);
+        assert_eq!(config.highlight, 2);
+        assert!(config.subtotal);
+
+        assert!(parse_marker("<!--- benchmarking table columns=foo --->").is_err());
+        assert!(parse_marker("<!--- benchmarking table 2023 bar --->").is_err());
+    }
+
+    #[test]
+    fn updates_tables_of_profile() {
+        let release = "<!--- benchmarking table release --->";
+        let debug = "<!--- benchmarking table debug --->";
+        let mut s = format!("{release}{release}\n{debug}{debug}");
+
+        update_content(&mut s, year!(2023), false, &get_mock_timings(), 190.0, None).unwrap();
+        assert!(s.starts_with(&format!("{release}{release}\n")));
+        assert!(s.contains("## Benchmarks (2023, debug)"));
+
+        let mut s = format!("{debug}{debug}");
+        assert!(
+            update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).is_err()
+        );
+    }
+
+    #[test]
+    fn formats_configured_tables() {
+        let marker =
+            "<!--- benchmarking table columns=status,samples sort=cost highlight=1 subtotal --->";
+        let mut s = format!("{marker}\n{marker}");
+        let mut timings = get_mock_timings();
+        timings[0].status_1 = Some(Status::Verified);
+        timings[0].status_2 = Some(Status::Mismatch("1".into()));
+
+        update_content(&mut s, year!(2023), true, &timings, 190.0, None).unwrap();
+        let expected = [
+            marker,
+            "## Benchmarks (2023)",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| **[Day 4](./src/bin/2023-04.rs)** | `40.0ms` <sub>n=100</sub> | `-` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` <sub>n=100</sub> | `40.0ms` <sub>n=100</sub> |",
+            "| [Day 1](./src/bin/2023-01.rs) | ✔ `10.0ms` <sub>n=100</sub> | ✘ `20.0ms` <sub>n=100</sub> |",
+            "| **Subtotal** | `80.0ms` | `60.0ms` |",
+            "",
+            "Median, number of samples per part. ✔ marks verified answers, ✘ changed ones. The slowest day is bold.",
+            "",
+            "**Total: 190.00ms**",
+            marker,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn ignores_mentions_of_markers() {
+        let mut s = format!("`<!--- benchmarking table <year> --->`\n{MARKER}{MARKER}");
+        update_content(&mut s, year!(2023), true, &get_mock_timings(), 190.0, None).unwrap();
+        assert!(s.contains("## Benchmarks (2023)"));
+    }
+
+    #[test]
+    fn keeps_parse_step_of_single_part() {
+        let mut s = "<!--- benchmarking table columns=status subtotal --->".to_string();
+        let timings = vec![Timings {
+            day: day!(1),
+            parse: mock_stats(5),
+            part_1: mock_stats(20),
+            part_2: None,
+            status_1: None,
+            status_2: None,
+            total_nanos: 2.5e+7,
+        }];
+        update_content(&mut s, year!(2023), true, &timings, 25.0, Some(1)).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `5.0ms` | `20.0ms` | _skipped_ |"));
+        assert!(s.contains("| **Subtotal** | `5.0ms` | `20.0ms` | _skipped_ |"));
+    }
+
+    #[test]
+    fn adds_parse_column() {
+        let mut s = format!("{MARKER}{MARKER}");
+        let mut timings = get_mock_timings();
+        timings[1].parse = mock_stats(5);
+
+        update_content(&mut s, year!(2023), true, &timings, 195.0, None).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `5.0ms ± 1.0ms` <sub>p95 7.0ms, n=100</sub> | `30.0ms"));
+    }
 }
@@ -0,0 +1,136 @@
+/// Module that collects the solutions registered by `solution!`, so that they can be run in-process.
+///
+/// Solutions only end up in the registry if they are linked into the running binary.
+/// The library links every solution of `src/bin` when it is built with the `in_process` feature.
+use std::any::Any;
+
+use crate::{Day, Year};
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Identifies the puzzle a [`Solution`] solves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Metadata {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Metadata {
+    /// Name of the bin of the solution, e.g. `2023-01`.
+    #[must_use]
+    pub fn bin_name(&self) -> String {
+        format!("{}-{}", self.year, self.day)
+    }
+}
+
+/// A solution of a day that can be run without spawning its bin.
+/// Implemented by `solution!` for every day.
+pub trait Solution: Sync {
+    fn metadata(&self) -> Metadata;
+
+    /// Prepares the input once before the parts run on it.
+    fn parse(&self, input: &str) -> Box<dyn Any>;
+
+    /// Whether the solution has a parse step of its own, which is then timed separately from the parts.
+    fn has_parse_step(&self) -> bool {
+        false
+    }
+
+    /// Runs part one on the output of [`Solution::parse`].
+    /// Returns the answer if the part found one, or the error the part returned.
+    fn part_one(&self, parsed: &dyn Any) -> Result<Option<String>, String>;
+
+    /// Runs part two on the output of [`Solution::parse`], see [`Solution::part_one`].
+    fn part_two(&self, parsed: &dyn Any) -> Result<Option<String>, String>;
+
+    /// Runs one of the parts on the output of [`Solution::parse`].
+    fn part(&self, part: u8, parsed: &dyn Any) -> Result<Option<String>, String> {
+        match part {
+            1 => self.part_one(parsed),
+            _ => self.part_two(parsed),
+        }
+    }
+}
+
+/// Downcasts the output of [`Solution::parse`] to the type returned by `parser`.
+#[doc(hidden)]
+#[must_use]
+pub fn downcast<T: 'static>(_parser: fn(&str) -> T, parsed: &dyn Any) -> Option<&T> {
+    parsed.downcast_ref()
+}
+
+/// An entry of the registry. Submitted by `solution!`.
+pub struct Registration(pub &'static dyn Solution);
+
+inventory::collect!(Registration);
+
+/// All registered solutions, ordered by year and day.
+#[must_use]
+pub fn solutions() -> Vec<&'static dyn Solution> {
+    let mut solutions: Vec<&'static dyn Solution> = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.0)
+        .collect();
+
+    solutions.sort_by_key(|solution| solution.metadata());
+    solutions
+}
+
+/// The registered solution of a day, if there is one.
+#[must_use]
+pub fn find(year: Year, day: Day) -> Option<&'static dyn Solution> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.0)
+        .find(|solution| solution.metadata() == Metadata { year, day })
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find, solutions, Metadata, Registration, Solution};
+    use crate::{day, year};
+    use std::any::Any;
+
+    struct Mock;
+
+    impl Solution for Mock {
+        fn metadata(&self) -> Metadata {
+            Metadata {
+                year: year!(2015),
+                day: day!(1),
+            }
+        }
+
+        fn parse(&self, input: &str) -> Box<dyn Any> {
+            Box::new(input.len())
+        }
+
+        fn part_one(&self, parsed: &dyn Any) -> Result<Option<String>, String> {
+            Ok(parsed.downcast_ref::<usize>().map(ToString::to_string))
+        }
+
+        fn part_two(&self, _: &dyn Any) -> Result<Option<String>, String> {
+            Err("no answer".into())
+        }
+    }
+
+    inventory::submit! { Registration(&Mock) }
+
+    #[test]
+    fn registers_solutions() {
+        let solution = find(year!(2015), day!(1)).unwrap();
+        assert_eq!(solution.metadata().bin_name(), "2015-01");
+        assert_eq!(
+            solution.part(1, &*solution.parse("abc")),
+            Ok(Some("3".into()))
+        );
+        assert_eq!(
+            solution.part(2, &*solution.parse("abc")),
+            Err("no answer".into())
+        );
+
+        assert!(find(year!(2015), day!(2)).is_none());
+        assert!(solutions().iter().any(|s| s.metadata().day == 1));
+    }
+}
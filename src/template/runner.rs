@@ -1,62 +1,764 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::allocator::{self, format_bytes, AllocStats};
+use crate::template::answers::{self, Verification};
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::json::{self, ObjectWriter};
+use crate::template::submissions::{self, Submission, Verdict};
+use crate::template::{
+    has_input, parse_duration, parse_part, parse_threads, prepare_input, try_read_file,
+    ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Day, Year};
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Printed next to a result that differs from the answer recorded in `data/answers`.
+const MISMATCH_MARKER: &str = "✘ mismatch";
+
+/// Prefix of the line that reports the spread of a benched part.
+const STATS_PREFIX: &str = "  ↳ ";
+
+/// Used in place of a part number in the report of the parse step that `solution!` shares between the parts.
+pub const PARSE_STEP: u8 = 0;
+
+/// What a solution part can return: `Option<T>`, `Result<T, E>` or `Result<Option<T>, E>`.
+/// An error is reported in place of the answer. It is formatted with `{:#}`, so that error types
+/// like `anyhow::Error` print their chain of causes.
+pub trait PartOutput {
+    type Answer: Display;
+
+    /// The answer of the part, if it found one, or the formatted error.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: AnswerValue, E: Display> PartOutput for Result<T, E> {
+    type Answer = T::Answer;
+
+    fn into_answer(self) -> Result<Option<T::Answer>, String> {
+        self.map(AnswerValue::into_option)
+            .map_err(|e| format!("{e:#}"))
+    }
+}
+
+/// The success value of a fallible part: a number, a string or an [`Option`] of anything that implements [`Display`].
+/// Implement it for an answer type of your own to return that type in a [`Result`].
+pub trait AnswerValue {
+    type Answer: Display;
+
+    fn into_option(self) -> Option<Self::Answer>;
+}
+
+impl<T: Display> AnswerValue for Option<T> {
+    type Answer = T;
+
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+macro_rules! impl_answer_value {
+    ($($t:ty),*) => {
+        $(
+            impl AnswerValue for $t {
+                type Answer = $t;
+
+                fn into_option(self) -> Option<$t> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_value!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    char,
+    String,
+    &'static str
+);
+
+/// Exit code of a day bin whose input file is missing or empty, so `cargo all` can skip the day
+/// instead of counting it as failed.
+pub const NO_INPUT_EXIT_CODE: i32 = 3;
+
+/// Reads the input of a day from the file passed with `--input <path>`, from stdin for `--input -`
+/// and from `data/YYYY/inputs/NN.txt` otherwise.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    match get_input_arg().as_deref() {
+        None => try_read_file("inputs", year, day).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(if has_input(year, day) {
+                1
+            } else {
+                NO_INPUT_EXIT_CODE
+            });
+        }),
+        Some("") => {
+            eprintln!("Unexpected command-line input. Format: --input <path>");
+            process::exit(1);
+        }
+        Some("-") => match io::read_to_string(io::stdin()) {
+            Ok(input) => check_input(&input, "stdin"),
+            Err(e) => {
+                eprintln!("Could not read input from stdin: {e}");
+                process::exit(1);
+            }
+        },
+        Some(path) => match fs::read_to_string(path) {
+            Ok(input) => check_input(&input, path),
+            Err(e) => {
+                eprintln!("Could not read input file \"{path}\": {e}");
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Exits with an error if an input passed with `--input` is empty, as a solution would likely panic on it.
+fn check_input(input: &str, source: &str) -> String {
+    if input.trim().is_empty() {
+        eprintln!("\"{source}\" is empty, expecting the puzzle input.");
+        process::exit(1);
+    }
+
+    prepare_input(input, source)
+}
+
+/// Sizes the global rayon pool to the value of `--threads`, if given. Must run before any parallel work.
+pub fn configure_threads() {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(threads) = get_arg_value(&args, "--threads") else {
+        return;
+    };
+
+    let threads = parse_threads(threads).unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}. Format: --threads 4");
+        process::exit(1);
+    });
+
+    if let Err(e) = set_threads(threads) {
+        eprintln!("Could not configure the thread pool: {e}");
+        process::exit(1);
+    }
+}
+
+/// Sizes the global rayon pool. This only works once per process, before the pool is first used.
+pub fn set_threads(threads: usize) -> Result<(), rayon::ThreadPoolBuildError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+}
+
+/// The value of `--part`, if only one part should run.
+fn get_part_arg() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+
+    get_arg_value(&args, "--part").map(|s| {
+        parse_part(s).unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}. Format: --part 2");
+            process::exit(1);
+        })
+    })
+}
+
+/// The value of `--input`, if the solution runs on an input other than our own.
+fn get_input_arg() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    get_arg_value(&args, "--input").map(String::from)
+}
+
+/// Runs a part in-process and returns its report, benching it with `bench_config` if given.
+/// Unlike [`run_part`], nothing is printed and command-line arguments are ignored.
+pub fn report_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    bench_config: Option<&BenchConfig>,
+) -> PartReport {
+    let func = |input| func(input).into_answer();
+
+    let ((result, base_time), alloc) = allocator::measure(|| {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        (result, timer.elapsed())
+    });
+
+    let stats = match bench_config {
+        Some(config) => bench(func, input, &base_time, config, OutputFormat::Json),
+        None => BenchStats::single(base_time),
+    };
+    let stats = BenchStats { alloc, ..stats };
+
+    let (answer, status) = match result {
+        Ok(result) => {
+            let answer = result.as_ref().map(ToString::to_string);
+            let status = get_status(answer.as_deref(), year, day, part);
+            (answer, status)
+        }
+        Err(error) => (None, Status::Error(error)),
+    };
+
+    PartReport {
+        day,
+        part,
+        answer,
+        status,
+        stats,
+    }
+}
+
+/// Runs the parse step in-process and returns its output together with its report, see [`report_part`].
+pub fn report_parse<T>(
+    func: impl Fn(&str) -> T,
+    input: &str,
+    day: Day,
+    bench_config: Option<&BenchConfig>,
+) -> (T, PartReport) {
+    let ((parsed, base_time), alloc) = allocator::measure(|| {
+        let timer = Instant::now();
+        let parsed = func(input);
+        (parsed, timer.elapsed())
+    });
+
+    let stats = match bench_config {
+        Some(config) => bench(func, input, &base_time, config, OutputFormat::Json),
+        None => BenchStats::single(base_time),
+    };
+
+    let report = PartReport {
+        day,
+        part: PARSE_STEP,
+        answer: None,
+        status: Status::Solved,
+        stats: BenchStats { alloc, ..stats },
+    };
+
+    (parsed, report)
+}
+
+/// Runs the parse step of a solution, timed like a part, and returns its output for the parts.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str, day: Day) -> T {
+    let format = OutputFormat::from_args();
+
+    let (parsed, stats) = run_timed(func, input, format, |_| {
+        if format == OutputFormat::Human {
+            print!("Parse");
+        }
+    });
+
+    let report = PartReport {
+        day,
+        part: PARSE_STEP,
+        answer: None,
+        status: Status::Solved,
+        stats,
+    };
+
+    match format {
+        OutputFormat::Human => report.print(),
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+
+    parsed
+}
+
+pub fn run_part<I: Clone + Send, O: PartOutput>(
+    func: impl Fn(I) -> O + Sync,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) {
+    if get_part_arg().is_some_and(|only| only != part) {
+        return;
+    }
+
+    let func = |input| func(input).into_answer();
+
+    let format = OutputFormat::from_args();
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(func, input.clone(), format, |result| {
+        if format == OutputFormat::Human {
+            let answer = result.as_ref().ok().and_then(Option::as_ref);
+            print_result(&answer, &part_str, "");
+        }
+    });
+
+    let (answer, status) = match &result {
+        Ok(result) => {
+            let answer = result.as_ref().map(ToString::to_string);
+            let status = match &answer {
+                // recorded answers only apply to our own input.
+                Some(_) if get_input_arg().is_some() => Status::Solved,
+                answer => get_status(answer.as_deref(), year, day, part),
+            };
+            (answer, status)
+        }
+        Err(error) => (None, Status::Error(error.clone())),
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let report = PartReport {
+        day,
+        part,
+        answer,
+        status,
+        stats,
+    };
+
+    match format {
+        OutputFormat::Human => report.print(),
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if format == OutputFormat::Human && env::args().any(|x| x == "--scaling") {
+        let scaling = bench_scaling(func, input, &stats.median, &BenchConfig::from_args());
+        println!("{}", format_scaling(&scaling));
     }
+
+    if let Ok(Some(result)) = result {
+        submit_result(result, year, day, part);
+    }
+}
+
+/// How results are written to stdout, set with `--format <human|json>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    /// One JSON object per part and line, see [`PartReport::to_json`].
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the output format from the arguments of the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        get_arg_value(&args, "--format").map_or(Self::Human, |s| {
+            s.parse().unwrap_or_else(|e| {
+                eprintln!("Unexpected command-line input: {e}. Format: --format json");
+                process::exit(1);
+            })
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err("expecting `human` or `json`"),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// The status of a solution part after it was run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned [`None`].
+    Unsolved,
+    /// The part returned an answer, but no answer is recorded for it.
+    Solved,
+    /// The answer matches the recorded answer.
+    Verified,
+    /// The answer differs from the recorded answer, which is kept here.
+    Mismatch(String),
+    /// The part returned an error, which is kept here.
+    Error(String),
+}
+
+impl Status {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Unsolved => "unsolved",
+            Status::Solved => "solved",
+            Status::Verified => "verified",
+            Status::Mismatch(_) => "mismatch",
+            Status::Error(_) => "error",
+        }
+    }
+}
+
+/// The outcome of running a single solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub stats: BenchStats,
+}
+
+impl PartReport {
+    /// Serializes the report as a single line of JSON, e.g.
+    /// `{"day":1,"part":1,"status":"verified","answer":"142","expected":null,"error":null,"duration_nanos":1200,"samples":1,...}`.
+    /// `duration_nanos` is the median duration, the remaining `*_nanos` fields describe the spread of the samples.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let expected = match &self.status {
+            Status::Mismatch(expected) => Some(expected.as_str()),
+            _ => None,
+        };
+        let error = match &self.status {
+            Status::Error(error) => Some(error.as_str()),
+            _ => None,
+        };
+
+        let writer = ObjectWriter::new()
+            .number("day", self.day.into_inner())
+            .number("part", self.part)
+            .string("status", self.status.as_str())
+            .opt_string("answer", self.answer.as_deref())
+            .opt_string("expected", expected)
+            .opt_string("error", error)
+            .number("duration_nanos", self.stats.median.as_nanos())
+            .number("samples", self.stats.samples)
+            .number("min_nanos", self.stats.min.as_nanos())
+            .number("max_nanos", self.stats.max.as_nanos())
+            .number("mean_nanos", self.stats.mean.as_nanos())
+            .number("stddev_nanos", self.stats.stddev.as_nanos())
+            .number("p95_nanos", self.stats.p95.as_nanos());
+
+        match self.stats.alloc {
+            Some(alloc) => writer
+                .number("allocations", alloc.allocations)
+                .number("allocated_bytes", alloc.bytes)
+                .number("peak_bytes", alloc.peak_bytes)
+                .finish(),
+            None => writer.finish(),
+        }
+    }
+
+    /// Parses a report written by [`PartReport::to_json`].
+    /// Returns [`None`] if the line is not a valid report.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+
+        let number = |key: &str| object.get(key)?.as_u128();
+        let duration = |key: &str| Some(Duration::from_nanos(u64::try_from(number(key)?).ok()?));
+        let string = |key: &str| object.get(key)?.as_str().map(String::from);
+        let alloc = || {
+            Some(AllocStats {
+                allocations: u64::try_from(number("allocations")?).ok()?,
+                bytes: u64::try_from(number("allocated_bytes")?).ok()?,
+                peak_bytes: u64::try_from(number("peak_bytes")?).ok()?,
+            })
+        };
+
+        let status = match object.get("status")?.as_str()? {
+            "unsolved" => Status::Unsolved,
+            "solved" => Status::Solved,
+            "verified" => Status::Verified,
+            "mismatch" => Status::Mismatch(string("expected")?),
+            "error" => Status::Error(string("error")?),
+            _ => return None,
+        };
+
+        Some(Self {
+            day: Day::new(u8::try_from(number("day")?).ok()?)?,
+            part: u8::try_from(number("part")?).ok()?,
+            answer: string("answer"),
+            status,
+            stats: BenchStats {
+                samples: number("samples")?,
+                mean: duration("mean_nanos")?,
+                median: duration("duration_nanos")?,
+                min: duration("min_nanos")?,
+                max: duration("max_nanos")?,
+                stddev: duration("stddev_nanos")?,
+                p95: duration("p95_nanos")?,
+                alloc: alloc(),
+            },
+        })
+    }
+
+    /// Whether this is the report of the parse step rather than of a part.
+    #[must_use]
+    pub fn is_parse_step(&self) -> bool {
+        self.part == PARSE_STEP
+    }
+
+    /// Prints the report in human-readable form.
+    pub fn print(&self) {
+        if self.is_parse_step() {
+            print!("\r");
+            println!(
+                "Parse:{}",
+                format_duration(&self.stats.median, self.stats.samples)
+            );
+            self.print_details();
+            return;
+        }
+
+        if let Status::Error(error) = &self.status {
+            print!("\r");
+            println!(
+                "Part {}: {ANSI_BOLD}✘ error{ANSI_RESET}{}",
+                self.part,
+                format_duration(&self.stats.median, self.stats.samples)
+            );
+            println!("{STATS_PREFIX}{error}");
+            return;
+        }
+
+        let suffix = match self.status {
+            Status::Unsolved => String::new(),
+            _ => format!(
+                "{}{}",
+                format_duration(&self.stats.median, self.stats.samples),
+                format_status(&self.status)
+            ),
+        };
+
+        print_result(&self.answer, &format!("Part {}", self.part), &suffix);
+
+        if self.answer.is_some() {
+            self.print_details();
+        }
+    }
+
+    /// Prints the spread and allocations of a benched part on the lines following its result.
+    fn print_details(&self) {
+        if self.stats.samples > 1 {
+            println!("{}", format_stats(&self.stats));
+        }
+
+        if let Some(alloc) = &self.stats.alloc {
+            println!("{}", format_alloc(alloc));
+        }
+    }
+}
+
+/// Summary statistics of the samples collected while benching a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    /// Allocations of the first run, if allocations are counted.
+    pub alloc: Option<AllocStats>,
+}
+
+impl BenchStats {
+    /// Stats for a part that was executed exactly once.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            stddev: Duration::ZERO,
+            p95: duration,
+            alloc: None,
+        }
+    }
+
+    /// Computes stats from a list of sample durations.
+    /// Returns [`None`] if `timers` is empty.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn from_samples(timers: &[Duration]) -> Option<Self> {
+        if timers.is_empty() {
+            return None;
+        }
+
+        let mut sorted = timers.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let mean_nanos = average_duration(&sorted);
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        // nearest-rank percentile.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        let variance = sorted
+            .iter()
+            .map(|x| (x.as_nanos() as f64 - mean_nanos as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Self {
+            samples: n as u128,
+            mean: Duration::from_nanos(mean_nanos as u64),
+            median,
+            min: sorted[0],
+            max: sorted[n - 1],
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+            p95,
+            alloc: None,
+        })
+    }
+}
+
+/// Options that control how parts are benched, passed as `--samples <n>` and `--budget <duration>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// A fixed number of samples to collect. Derived from the budget if not set.
+    pub samples: Option<u32>,
+    /// The approximate time budget for a bench. Defaults to 1 second.
+    pub budget: Option<Duration>,
+}
+
+impl BenchConfig {
+    /// Reads the bench options from the arguments of the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let samples = get_arg_value(&args, "--samples").map(|s| {
+            s.parse().unwrap_or_else(|_| {
+                eprintln!("Unexpected command-line input. Format: --samples 100");
+                process::exit(1);
+            })
+        });
+
+        let budget = get_arg_value(&args, "--budget").map(|s| {
+            parse_duration(s).unwrap_or_else(|e| {
+                eprintln!("Unexpected command-line input: {e}. Format: --budget 2s");
+                process::exit(1);
+            })
+        });
+
+        Self { samples, budget }
+    }
+
+    /// Converts the options back into command-line arguments for child invocations.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(samples) = self.samples {
+            args.push("--samples".into());
+            args.push(samples.to_string());
+        }
+
+        if let Some(budget) = self.budget {
+            args.push("--budget".into());
+            args.push(format!("{}ns", budget.as_nanos()));
+        }
+
+        args
+    }
+
+    fn budget(&self) -> Duration {
+        self.budget.unwrap_or(Duration::from_secs(1))
+    }
+}
+
+fn get_arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|x| x == name)?;
+    Some(args.get(index + 1).map_or("", String::as_str))
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched (approx. the time budget of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    format: OutputFormat,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> (T, BenchStats) {
+    let ((result, base_time), alloc) = allocator::measure(|| {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        (result, timer.elapsed())
+    });
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if std::env::args().any(|x| x == "--time") {
+        bench(func, input, &base_time, &BenchConfig::from_args(), format)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, BenchStats { alloc, ..stats })
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Benches a function after warming it up for up to a tenth of the time budget.
+/// Unless a fixed sample count is configured, the number of samples is derived from the budget and the duration of the first run.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    format: OutputFormat,
+) -> BenchStats {
+    if format == OutputFormat::Human {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let budget = config.budget();
+    let warmup_timer = Instant::now();
+    let mut warmup_iterations = 0;
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    while warmup_iterations < 100 && warmup_timer.elapsed() + *base_time < budget / 10 {
+        func(input.clone());
+        warmup_iterations += 1;
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
+    let bench_iterations = config.samples.map_or_else(
+        || (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000),
+        |samples| u128::from(cmp::max(samples, 1)),
     );
 
     let mut timers: Vec<Duration> = vec![];
@@ -69,11 +771,62 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: at least one sample is always collected.
+    BenchStats::from_samples(&timers).unwrap()
+}
+
+/// Thread counts a part is benched with by `--scaling`: 1, 2, 4 and all available threads.
+fn get_scaling_threads() -> Vec<usize> {
+    let all = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let mut threads: Vec<usize> = [1, 2, 4].into_iter().filter(|&n| n < all).collect();
+    threads.push(all);
+    threads
+}
+
+/// Benches a function in a dedicated thread pool for each of the [`get_scaling_threads`].
+fn bench_scaling<I: Clone + Send, T>(
+    func: impl Fn(I) -> T + Sync,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Vec<(usize, BenchStats)> {
+    get_scaling_threads()
+        .into_iter()
+        .filter_map(|threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .ok()?;
+            let input = input.clone();
+            let stats = pool.install(|| bench(&func, input, base_time, config, OutputFormat::Json));
+            Some((threads, stats))
+        })
+        .collect()
+}
+
+/// Formats the median of each thread count with its speedup over a single thread.
+#[allow(clippy::cast_precision_loss)]
+fn format_scaling(scaling: &[(usize, BenchStats)]) -> String {
+    let single = scaling.first().map(|(_, stats)| stats.median);
+
+    let cells: Vec<String> = scaling
+        .iter()
+        .map(|(threads, stats)| {
+            let unit = if *threads == 1 { "thread" } else { "threads" };
+            let speedup = match single {
+                Some(single) if *threads > 1 && !stats.median.is_zero() => {
+                    format!(
+                        " ({:.2}x)",
+                        single.as_nanos() as f64 / stats.median.as_nanos() as f64
+                    )
+                }
+                _ => String::new(),
+            };
+            format!("{threads} {unit} {:.1?}{speedup}", stats.median)
+        })
+        .collect();
+
+    format!("{STATS_PREFIX}{}", cells.join(" | "))
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -92,6 +845,57 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+/// Formats the spread of a bench, printed on the line following the result.
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "{STATS_PREFIX}min {:.1?} | max {:.1?} | σ {:.1?} | p95 {:.1?} | mean {:.1?}",
+        stats.min, stats.max, stats.stddev, stats.p95, stats.mean
+    )
+}
+
+fn format_alloc(alloc: &AllocStats) -> String {
+    format!(
+        "{STATS_PREFIX}{} allocations | {} allocated | {} peak",
+        alloc.allocations,
+        format_bytes(alloc.bytes),
+        format_bytes(alloc.peak_bytes)
+    )
+}
+
+fn get_status(answer: Option<&str>, year: Year, day: Day, part: u8) -> Status {
+    match answer {
+        Some(answer) => match verify_result(answer, year, day, part) {
+            Verification::Verified => Status::Verified,
+            Verification::Mismatch(expected) => Status::Mismatch(expected),
+            Verification::Unknown => Status::Solved,
+        },
+        None => Status::Unsolved,
+    }
+}
+
+fn verify_result(result: &str, year: Year, day: Day, part: u8) -> Verification {
+    match answers::read(year, day) {
+        Ok(answers) => answers.verify(part, result),
+        Err(e) => {
+            eprintln!("Could not read recorded answers: {e:?}");
+            Verification::Unknown
+        }
+    }
+}
+
+fn format_status(status: &Status) -> String {
+    match status {
+        Status::Verified => " ✔ verified".into(),
+        Status::Mismatch(expected) => {
+            format!(
+                " {MISMATCH_MARKER} (expected `{}`)",
+                expected.escape_debug()
+            )
+        }
+        Status::Solved | Status::Unsolved | Status::Error(_) => String::new(),
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -128,13 +932,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  1. `--submit <part>` matches the part that was run.
+///  2. a session cookie is configured.
+///  3. the local submission history does not rule out the answer.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +963,131 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if get_input_arg().is_some() {
+        eprintln!("Not submitting: the solution ran on a custom input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+
+    let history = submissions::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Could not read submission history: {e:?}");
         process::exit(1);
+    });
+
+    if let Err(refusal) = history.check(part, &answer, submissions::now()) {
+        eprintln!("Not submitting: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up Advent of Code client: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let response = client.submit(day, part, &answer);
+
+    match &response {
+        Ok(message) => {
+            println!("{message}");
+            record_submission(year, day, part, &answer, message);
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
+    }
+
+    Some(response)
+}
+
+/// Logs a submission and stores correct answers in the answer store.
+fn record_submission(year: Year, day: Day, part: u8, answer: &str, message: &str) {
+    let response = submissions::parse_response(message);
+    let timestamp = submissions::now();
+
+    let submission = Submission {
+        timestamp,
+        part,
+        answer: answer.to_string(),
+        verdict: response.verdict,
+        cooldown_until: response.wait.map(|wait| timestamp + wait.as_secs()),
+    };
+
+    if let Err(e) = submissions::record(year, day, &submission) {
+        eprintln!("Could not record submission: {e:?}");
+    }
+
+    if response.verdict == Verdict::Correct {
+        if let Err(e) = answers::record(year, day, part, answer) {
+            eprintln!("Could not record answer: {e:?}");
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_scaling, BenchStats, PartOutput};
+    use std::{fmt, time::Duration};
+
+    /// An error that, like `anyhow::Error`, prints its causes in the alternate form.
+    struct ChainedError;
+
+    impl fmt::Display for ChainedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if f.alternate() {
+                write!(f, "invalid direction: expected L or R")
+            } else {
+                write!(f, "invalid direction")
+            }
+        }
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42_u64).into_answer(), Ok(Some(42)));
+        assert_eq!(None::<u64>.into_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(42_u32).into_answer(), Ok(Some(42)));
+        assert_eq!(Ok::<_, String>(Some("a")).into_answer(), Ok(Some("a")));
+        assert_eq!(Ok::<Option<u8>, String>(None).into_answer(), Ok(None));
+        assert_eq!(
+            Err::<u32, _>(ChainedError).into_answer(),
+            Err("invalid direction: expected L or R".into())
+        );
+    }
+
+    #[test]
+    fn formats_scaling() {
+        let stats = |millis| BenchStats::single(Duration::from_millis(millis));
+        assert_eq!(
+            format_scaling(&[(1, stats(12)), (2, stats(8)), (4, stats(4))]),
+            "  ↳ 1 thread 12.0ms | 2 threads 8.0ms (1.50x) | 4 threads 4.0ms (3.00x)"
+        );
+    }
+
+    #[test]
+    fn computes_stats_from_samples() {
+        let timers: Vec<Duration> = [40, 10, 30, 20, 100]
+            .iter()
+            .map(|&x| Duration::from_nanos(x))
+            .collect();
+
+        let stats = BenchStats::from_samples(&timers).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.mean, Duration::from_nanos(40));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.stddev, Duration::from_nanos(31));
+    }
+
+    #[test]
+    fn computes_median_of_even_samples() {
+        let timers = [Duration::from_nanos(10), Duration::from_nanos(20)];
+        let stats = BenchStats::from_samples(&timers).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(15));
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
 }
@@ -0,0 +1,427 @@
+/// Module that keeps a local log of submitted answers and guards against pointless submissions.
+/// Every submission is appended to `data/YYYY/submissions/NN.jsonl` together with the verdict of the server.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::json::{self, ObjectWriter};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The server's judgement of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer was not judged because the previous submission was too recent.
+    TooRecent,
+    /// The part was already solved.
+    AlreadyCompleted,
+    Unknown,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::TooRecent => "too_recent",
+            Verdict::AlreadyCompleted => "already_completed",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "correct" => Verdict::Correct,
+            "incorrect" => Verdict::Incorrect,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "too_recent" => Verdict::TooRecent,
+            "already_completed" => Verdict::AlreadyCompleted,
+            "unknown" => Verdict::Unknown,
+            _ => return None,
+        })
+    }
+
+    #[must_use]
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+/// The parsed response to a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long the server asks us to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+/// Parses the text of the server's response to a submission.
+#[must_use]
+pub fn parse_response(message: &str) -> Response {
+    let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let verdict = if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::TooRecent
+    } else if message.contains("Did you already complete it") {
+        Verdict::AlreadyCompleted
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if message.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else {
+        Verdict::Unknown
+    };
+
+    Response {
+        verdict,
+        wait: parse_wait(&message),
+    }
+}
+
+/// Finds the cooldown in messages such as "please wait one minute before trying again"
+/// or "You have 4m 32s left to wait".
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some((before, _)) = message.split_once(" left to wait") {
+        let secs = before
+            .rsplit("You have ")
+            .next()?
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "s" => Some(value),
+                    "m" => Some(value * 60),
+                    "h" => Some(value * 3600),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let (_, after) = message.split_once("wait ")?;
+    let mut words = after.split_whitespace();
+    let value = match words.next()? {
+        "one" | "a" => 1,
+        n => n.parse().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(Duration::from_secs(value * 60)),
+        unit if unit.starts_with("second") => Some(Duration::from_secs(value)),
+        _ => None,
+    }
+}
+
+/// A recorded submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch until which no further answer should be submitted.
+    pub cooldown_until: Option<u64>,
+}
+
+impl Submission {
+    fn to_json(&self) -> String {
+        let writer = ObjectWriter::new()
+            .number("timestamp", self.timestamp)
+            .number("part", self.part)
+            .string("answer", &self.answer)
+            .string("verdict", self.verdict.as_str());
+
+        match self.cooldown_until {
+            Some(cooldown_until) => writer.number("cooldown_until", cooldown_until),
+            None => writer,
+        }
+        .finish()
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line)?;
+        let number = |key: &str| object.get(key)?.as_u128();
+
+        Some(Self {
+            timestamp: u64::try_from(number("timestamp")?).ok()?,
+            part: u8::try_from(number("part")?).ok()?,
+            answer: object.get("answer")?.as_str()?.to_string(),
+            verdict: Verdict::parse(object.get("verdict")?.as_str()?)?,
+            cooldown_until: number("cooldown_until").and_then(|n| u64::try_from(n).ok()),
+        })
+    }
+}
+
+/// Reasons to not submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect(String),
+    KnownWrong,
+    NotBelow(String),
+    NotAbove(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than `{bound}`, which was too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than `{bound}`, which was too low."
+                )
+            }
+            Refusal::Cooldown(wait) => write!(
+                f,
+                "the server asked to wait, try again in {}s.",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+/// All recorded submissions of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Checks whether an answer for `part` is worth submitting at time `now` (seconds since the unix epoch).
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let for_part = || self.submissions.iter().filter(move |s| s.part == part);
+
+        if let Some(correct) = for_part().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadyCorrect(correct.answer.clone()));
+        }
+
+        if for_part().any(|s| s.verdict.is_wrong() && s.answer == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let numeric_answers = |verdict: Verdict| {
+                for_part()
+                    .filter(move |s| s.verdict == verdict)
+                    .filter_map(|s| s.answer.parse::<i128>().ok())
+            };
+
+            if let Some(too_high) = numeric_answers(Verdict::TooHigh).min() {
+                if value >= too_high {
+                    return Err(Refusal::NotBelow(too_high.to_string()));
+                }
+            }
+
+            if let Some(too_low) = numeric_answers(Verdict::TooLow).max() {
+                if value <= too_low {
+                    return Err(Refusal::NotAbove(too_low.to_string()));
+                }
+            }
+        }
+
+        // the cooldown applies to all parts of all days, but we only know about this day.
+        if let Some(cooldown_until) = self
+            .submissions
+            .iter()
+            .filter_map(|s| s.cooldown_until)
+            .max()
+        {
+            if cooldown_until > now {
+                return Err(Refusal::Cooldown(Duration::from_secs(cooldown_until - now)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_path_for_submissions(year: Year, day: Day) -> String {
+    format!("data/{year}/submissions/{day}.jsonl")
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Reads the submission history of a day. A missing file means nothing was submitted yet.
+pub fn read(year: Year, day: Day) -> Result<History, Error> {
+    let content = match fs::read_to_string(get_path_for_submissions(year, day)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let submissions = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            Submission::from_json(line)
+                .ok_or_else(|| Error::Parser(format!("line {}: invalid submission.", i + 1)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(History { submissions })
+}
+
+/// Appends a submission to the log of a day.
+pub fn record(year: Year, day: Day, submission: &Submission) -> Result<(), Error> {
+    let path = get_path_for_submissions(year, day);
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", submission.to_json())?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{parse_response, History, Refusal, Submission, Verdict};
+    use std::time::Duration;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1000,
+            part,
+            answer: answer.into(),
+            verdict,
+            cooldown_until: None,
+        }
+    }
+
+    #[test]
+    fn parses_responses() {
+        let response = parse_response("That's the right answer! You are one gold star closer.");
+        assert_eq!(response.verdict, Verdict::Correct);
+        assert_eq!(response.wait, None);
+
+        let response = parse_response(
+            "That's not the right answer; your answer is too high. \
+             If you're stuck, make sure you're using the full input data. \
+             Please wait one minute before trying again.",
+        );
+        assert_eq!(response.verdict, Verdict::TooHigh);
+        assert_eq!(response.wait, Some(Duration::from_secs(60)));
+
+        let response = parse_response(
+            "That's not the right answer. please wait 5 minutes before trying again.",
+        );
+        assert_eq!(response.verdict, Verdict::Incorrect);
+        assert_eq!(response.wait, Some(Duration::from_secs(300)));
+
+        let response = parse_response(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 4m 32s left to wait.",
+        );
+        assert_eq!(response.verdict, Verdict::TooRecent);
+        assert_eq!(response.wait, Some(Duration::from_secs(272)));
+
+        let response = parse_response(
+            "You don't seem to be solving the right level.  Did you already complete it?",
+        );
+        assert_eq!(response.verdict, Verdict::AlreadyCompleted);
+    }
+
+    #[test]
+    fn refuses_known_and_bounded_answers() {
+        let history = History {
+            submissions: vec![
+                submission(1, "abc", Verdict::Incorrect),
+                submission(1, "500", Verdict::TooHigh),
+                submission(1, "100", Verdict::TooLow),
+                submission(2, "7", Verdict::Correct),
+            ],
+        };
+
+        assert_eq!(history.check(1, "abc", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(history.check(1, "500", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(
+            history.check(1, "600", 2000),
+            Err(Refusal::NotBelow("500".into()))
+        );
+        assert_eq!(
+            history.check(1, "50", 2000),
+            Err(Refusal::NotAbove("100".into()))
+        );
+        assert_eq!(history.check(1, "250", 2000), Ok(()));
+        assert_eq!(
+            history.check(2, "8", 2000),
+            Err(Refusal::AlreadyCorrect("7".into()))
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let mut wrong = submission(1, "1", Verdict::Incorrect);
+        wrong.cooldown_until = Some(1060);
+        let history = History {
+            submissions: vec![wrong],
+        };
+
+        assert_eq!(
+            history.check(1, "2", 1030),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(history.check(1, "2", 1060), Ok(()));
+    }
+
+    #[test]
+    fn submissions_survive_round_trip() {
+        let mut original = submission(2, "multi\nline", Verdict::TooLow);
+        original.cooldown_until = Some(1060);
+        assert_eq!(Submission::from_json(&original.to_json()), Some(original));
+    }
+}
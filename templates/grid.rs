@@ -0,0 +1,32 @@
+use rayon::prelude::*;
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse = parse_grid);
+
+type Grid = Vec<Vec<u8>>;
+
+pub fn parse_grid(input: &str) -> Grid {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+/// The orthogonal neighbours of a cell that lie within the grid.
+fn neighbours(grid: &Grid, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+    [(0, -1), (1, 0), (0, 1), (-1, 0)]
+        .into_iter()
+        .filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            (y < grid.len() && x < grid[y].len()).then_some((x, y))
+        })
+}
+
+pub fn part_one(grid: &Grid) -> Option<ANSWER_TYPE> {
+    None
+}
+
+pub fn part_two(grid: &Grid) -> Option<ANSWER_TYPE> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+TESTS}
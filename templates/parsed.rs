@@ -0,0 +1,25 @@
+use rayon::prelude::*;
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse = parse);
+
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Puzzle {
+    Puzzle {
+        lines: input.lines().map(String::from).collect(),
+    }
+}
+
+pub fn part_one(puzzle: &Puzzle) -> Option<ANSWER_TYPE> {
+    None
+}
+
+pub fn part_two(puzzle: &Puzzle) -> Option<ANSWER_TYPE> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+TESTS}
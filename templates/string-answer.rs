@@ -0,0 +1,16 @@
+// answer: String
+use rayon::prelude::*;
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<String> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+TESTS}